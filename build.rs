@@ -0,0 +1,30 @@
+// checks content/portfolio.toml against the schema in src/app/portfolio.rs
+// and embeds it as JSON, so bad content fails the build instead of the page
+
+use std::{env, fs, path::Path, process};
+
+#[allow(dead_code)]
+#[path = "src/app/portfolio.rs"]
+mod portfolio;
+
+const CONTENT: &str = "content/portfolio.toml";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", CONTENT);
+    println!("cargo:rerun-if-changed=src/app/portfolio.rs");
+
+    let raw = fs::read_to_string(CONTENT).unwrap_or_else(|err| fail(&format!("cannot read it: {}", err)));
+    let portfolio: portfolio::Portfolio = toml::from_str(&raw).unwrap_or_else(|err| fail(&err.to_string()));
+    if let Err(errors) = portfolio.validate() {
+        fail(&errors.join("\n"));
+    }
+
+    let json = serde_json::to_string(&portfolio).expect("portfolio serializes");
+    let out = Path::new(&env::var("OUT_DIR").expect("OUT_DIR is set by cargo")).join("portfolio.json");
+    fs::write(out, json).unwrap_or_else(|err| fail(&format!("cannot write the embedded copy: {}", err)));
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {} is invalid\n{}", CONTENT, message);
+    process::exit(1);
+}
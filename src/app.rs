@@ -4,6 +4,32 @@ use leptos::html::Div;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use wasm_bindgen::JsCast;
 
+mod ansi;
+mod commands;
+mod completion;
+mod content;
+mod history;
+mod portfolio;
+mod readline;
+mod screen;
+mod scrollback;
+mod table;
+mod tmpfs;
+mod timeline;
+mod tokenizer;
+mod vfs;
+
+use commands::{Category, Context, Output, Registry};
+use completion::complete;
+use content::portfolio;
+use history::{CommandHistory, ReverseSearch};
+use portfolio::{Domain, Project, Skill, SkillArea};
+use readline::EditLine;
+use scrollback::{Anchor, Scrollback};
+use table::{Align, Border, Cell, Table};
+use timeline::{Step, Timeline};
+use tokenizer::{parse, quote, tokenize};
+
 static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
 
 // main app component - keeps it simple
@@ -23,8 +49,39 @@ enum OutputPart {
     Bold(String),
     Section(String),
     Badge(String),
+    // a badge that runs `command` when clicked
+    CmdBadge { text: String, command: String },
     CmdName(String),
     Link { text: String, url: String },
+    // a search match, e.g. what grep found
+    Highlight(String),
+    // text colored by ANSI escapes
+    Styled { text: String, style: ansi::Style },
+    // a divider of repeated `fill`, redrawn to the terminal width - `text`
+    // is the width it had when the line was made, what pipes see
+    Rule { fill: String, text: String },
+    // a paragraph re-wrapped to the terminal width, later rows start with
+    // `hang`. pipes see it as one long line
+    Wrap { text: String, hang: String },
+}
+
+impl OutputPart {
+    // the characters the part shows, without styling
+    fn text(&self) -> &str {
+        match self {
+            OutputPart::Text(text)
+            | OutputPart::Bold(text)
+            | OutputPart::Section(text)
+            | OutputPart::Badge(text)
+            | OutputPart::CmdBadge { text, .. }
+            | OutputPart::CmdName(text)
+            | OutputPart::Link { text, .. }
+            | OutputPart::Highlight(text)
+            | OutputPart::Styled { text, .. }
+            | OutputPart::Rule { text, .. }
+            | OutputPart::Wrap { text, .. } => text,
+        }
+    }
 }
 
 #[derive(Clone, PartialEq)]
@@ -37,16 +94,57 @@ struct TerminalLine {
 }
 
 impl TerminalLine {
+    // ANSI escapes in `content` are turned into styled parts
     fn text(prefix: &str, content: &str, is_boot: bool) -> Self {
         Self {
             id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
             prefix: prefix.to_string(),
-            parts: vec![OutputPart::Text(content.to_string())],
+            parts: ansi::parse(content),
             is_command: false,
             is_boot,
         }
     }
 
+    // a line assembled from already styled parts
+    fn parts(prefix: &str, parts: Vec<OutputPart>) -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            prefix: prefix.to_string(),
+            parts,
+            is_command: false,
+            is_boot: false,
+        }
+    }
+
+    // a divider filling the terminal, with the same margin on both sides
+    fn rule(indent: &str, fill: &str) -> Self {
+        let text = screen::rule(fill, screen::columns().saturating_sub(2 * table::width(indent)));
+        Self::parts("", vec![OutputPart::Text(indent.to_string()), OutputPart::Rule { fill: fill.to_string(), text }])
+    }
+
+    // `first` then `content`, wrapped so later rows line up under `hang`
+    // text with ANSI escapes keeps its styling and is left to the browser
+    fn paragraph(first: &str, hang: &str, content: &str) -> Self {
+        if content.contains('\x1b') {
+            return Self::text("", &format!("{}{}", first, content), false);
+        }
+        Self::parts("", vec![
+            OutputPart::Text(first.to_string()),
+            OutputPart::Wrap { text: content.to_string(), hang: hang.to_string() },
+        ])
+    }
+
+    // an echoed command line, shown after the prompt
+    fn command(line: &str) -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            prefix: "λ".to_string(),
+            parts: vec![OutputPart::Text(format!(" {}", line))],
+            is_command: true,
+            is_boot: false,
+        }
+    }
+
     fn bold(prefix: &str, content: &str) -> Self {
         Self {
             id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
@@ -81,6 +179,21 @@ impl TerminalLine {
         }
     }
 
+    // badges that link to `skills <name>` for each item
+    fn skill_badges(prefix: &str, items: &[&str]) -> Self {
+        let parts = items.iter()
+            .map(|s| OutputPart::CmdBadge { text: s.to_string(), command: format!("skills {}", quote(s)) })
+            .collect();
+
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            prefix: prefix.to_string(),
+            parts,
+            is_command: false,
+            is_boot: false,
+        }
+    }
+
     fn help_entry(indent: &str, cmd: &str, desc: &str) -> Self {
         Self {
             id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
@@ -111,92 +224,125 @@ impl TerminalLine {
             is_boot: false,
         }
     }
+
+    // several links on one line, separated by `sep`
+    fn links(prefix: &str, indent: &str, links: &[(&str, &str)], sep: &str) -> Self {
+        let mut parts = vec![OutputPart::Text(indent.to_string())];
+        for (i, (text, url)) in links.iter().enumerate() {
+            if i > 0 {
+                parts.push(OutputPart::Text(sep.to_string()));
+            }
+            parts.push(OutputPart::Link { text: text.to_string(), url: url.to_string() });
+        }
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            prefix: prefix.to_string(),
+            parts,
+            is_command: false,
+            is_boot: false,
+        }
+    }
+
+    // what the line reads as once styling is stripped - used by pipes
+    fn plain_text(&self) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                // badges render with a gap between them
+                OutputPart::Badge(text) | OutputPart::CmdBadge { text, .. } => {
+                    if !out.is_empty() && !out.ends_with(' ') {
+                        out.push(' ');
+                    }
+                    out.push_str(text);
+                }
+                part => out.push_str(part.text()),
+            }
+        }
+        out
+    }
+
+    // the same line with each match of `pattern` in its plain text parts
+    // marked, styled parts (links, badges) are left alone
+    fn highlighted(&self, pattern: &str, ignore_case: bool) -> Self {
+        let mut line = self.clone();
+        line.parts = vec![];
+        for part in &self.parts {
+            // a matched paragraph gives up reflowing to show its matches
+            let (OutputPart::Text(text) | OutputPart::Wrap { text, .. }) = part else {
+                line.parts.push(part.clone());
+                continue;
+            };
+            let matches = find_matches(text, pattern, ignore_case);
+            if matches.is_empty() {
+                line.parts.push(part.clone());
+                continue;
+            }
+            let mut last = 0;
+            for (start, end) in matches {
+                if start > last {
+                    line.parts.push(OutputPart::Text(text[last..start].to_string()));
+                }
+                line.parts.push(OutputPart::Highlight(text[start..end].to_string()));
+                last = end;
+            }
+            if last < text.len() {
+                line.parts.push(OutputPart::Text(text[last..].to_string()));
+            }
+        }
+        line
+    }
+}
+
+// clear the screen and play the boot sequence, the prompt appears once it
+// is done - on load and for `reboot`
+fn boot(registry: &Registry, timeline: Timeline, set_history: WriteSignal<Scrollback>, set_show_input: WriteSignal<bool>) {
+    let steps = get_boot_sequence(registry)
+        .into_iter()
+        .map(|(delay, line)| (delay, Box::new(move || set_history.update(|h| h.push(line))) as Step))
+        .collect();
+    timeline.cancel();
+    set_show_input.set(false);
+    set_history.update(Scrollback::clear);
+    timeline.play(steps, Box::new(move || set_show_input.set(true)));
 }
 
 // boot sequence - simulates linux startup
-fn get_boot_sequence() -> Vec<(u64, TerminalLine)> {
-    vec![
+fn get_boot_sequence(registry: &Registry) -> Vec<(u64, TerminalLine)> {
+    let mut boot = vec![
         (100, TerminalLine::text("[    0.000]", " Initializing portfolio kernel...", true)),
         (350, TerminalLine::text("[    0.127]", " Loading modules: rust_core, wasm_runtime", true)),
         (600, TerminalLine::text("[    0.256]", " Mounting portfolio filesystem... OK", true)),
         (850, TerminalLine::text("[    0.384]", " Starting services... DONE", true)),
         (1100, TerminalLine::text("[    0.512]", " Authenticating... VERIFIED", true)),
         (1400, TerminalLine::text("", "", false)),
-        (1700, TerminalLine::text("", "  CM MONGO", false)),
-        (1800, TerminalLine::text("", "  Independent Systems Engineer | Edge Computing", false)),
+        (1700, TerminalLine::text("", &format!("  {}", portfolio().profile.banner), false)),
+        (1800, TerminalLine::text("", &format!("  {}", portfolio().profile.tagline), false)),
         (2000, TerminalLine::text("", "", false)),
         (2300, TerminalLine::text("[OK]", " System Ready.", true)),
         (2400, TerminalLine::text("", "", false)),
         (2500, TerminalLine::section("", "  > AVAILABLE COMMANDS")),
-        (2600, TerminalLine {
-            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-            prefix: "".to_string(),
-            parts: vec![
-                OutputPart::Bold("  projects".to_string()),
-                OutputPart::Text("   View portfolio projects".to_string()),
-            ],
-            is_command: false,
-            is_boot: false,
-        }),
-        (2700, TerminalLine {
-            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-            prefix: "".to_string(),
-            parts: vec![
-                OutputPart::Bold("  skills".to_string()),
-                OutputPart::Text("     Technical stack".to_string()),
-            ],
-            is_command: false,
-            is_boot: false,
-        }),
-        (2800, TerminalLine {
-            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-            prefix: "".to_string(),
-            parts: vec![
-                OutputPart::Bold("  about".to_string()),
-                OutputPart::Text("      Profile and bio".to_string()),
-            ],
-            is_command: false,
-            is_boot: false,
-        }),
-        (2900, TerminalLine {
-            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-            prefix: "".to_string(),
-            parts: vec![
-                OutputPart::Bold("  contact".to_string()),
-                OutputPart::Text("    Get in touch".to_string()),
-            ],
-            is_command: false,
-            is_boot: false,
-        }),
-        (3000, TerminalLine {
-            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-            prefix: "".to_string(),
-            parts: vec![
-                OutputPart::Bold("  clear".to_string()),
-                OutputPart::Text("      Reset terminal".to_string()),
-            ],
-            is_command: false,
-            is_boot: false,
-        }),
-        (3050, TerminalLine {
-            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-            prefix: "".to_string(),
-            parts: vec![
-                OutputPart::Bold("  help".to_string()),
-                OutputPart::Text("       List all commands".to_string()),
-            ],
-            is_command: false,
-            is_boot: false,
-        }),
-        (3100, TerminalLine::text("", "", false)),
-        (3200, TerminalLine::text("", "", false)),
-    ]
+    ];
+    // command menu comes straight from the registry, 100ms apart
+    let mut delay = 2500;
+    for line in registry.menu_lines(Category::Core) {
+        delay += 100;
+        boot.push((delay, line));
+    }
+    boot.push((delay + 100, TerminalLine::text("", "", false)));
+    boot.push((delay + 200, TerminalLine::text("", "", false)));
+    boot
 }
 
 
 
 
 
+// the page's localStorage, shared by everything that persists across
+// reloads - None when the browser refuses it (e.g. storage disabled)
+fn storage() -> Option<web_sys::Storage> {
+    window().local_storage().ok().flatten()
+}
+
 // Levenshtein distance for fuzzy matching
 fn levenshtein(a: &str, b: &str) -> usize {
     let len_a = a.chars().count();
@@ -206,8 +352,8 @@ fn levenshtein(a: &str, b: &str) -> usize {
 
     let mut matrix = vec![vec![0; len_b + 1]; len_a + 1];
 
-    for i in 0..=len_a { matrix[i][0] = i; }
-    for j in 0..=len_b { matrix[0][j] = j; }
+    for (i, row) in matrix.iter_mut().enumerate() { row[0] = i; }
+    for (j, cell) in matrix[0].iter_mut().enumerate() { *cell = j; }
 
     for (i, char_a) in a.chars().enumerate() {
         for (j, char_b) in b.chars().enumerate() {
@@ -221,436 +367,484 @@ fn levenshtein(a: &str, b: &str) -> usize {
     matrix[len_a][len_b]
 }
 
+// byte ranges of the non-overlapping occurrences of `pattern` in `text`,
+// compared char by char so case folding never shifts the offsets. an empty
+// pattern matches every line but has nothing to highlight, so no ranges
+fn find_matches(text: &str, pattern: &str, ignore_case: bool) -> Vec<(usize, usize)> {
+    if pattern.is_empty() {
+        return vec![];
+    }
+    let same = |a: char, b: char| if ignore_case { a.to_lowercase().eq(b.to_lowercase()) } else { a == b };
+    let mut matches = vec![];
+    let mut pos = 0;
+    while let Some(first) = text[pos..].chars().next() {
+        let mut chars = text[pos..].char_indices();
+        let mut end = Some(pos);
+        for p in pattern.chars() {
+            match chars.next() {
+                Some((i, c)) if same(c, p) => end = Some(pos + i + c.len_utf8()),
+                _ => {
+                    end = None;
+                    break;
+                }
+            }
+        }
+        match end {
+            Some(end) if end > pos => {
+                matches.push((pos, end));
+                pos = end;
+            }
+            _ => pos += first.len_utf8(),
+        }
+    }
+    matches
+}
+
 // portfolio projects - organized by technical domain
 // showcases progression from full-stack to systems-level edge computing
-fn get_projects_output() -> Vec<TerminalLine> {
-    let divider = "  - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -";
-    vec![
-        TerminalLine::text("", "", false),
-        // flagship industrial edge projects - wasi 0.2 expertise
-        TerminalLine::section("", "  // INDUSTRIAL EDGE"),
-        TerminalLine::text("", "  ═══════════════════════════════════════════════════════════", false),
-        TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  Guardian One Platform"),
-        TerminalLine::text("", "  └─ Industrial edge security: WASM vs Docker comparison", false),
-        TerminalLine::text("", "     2oo3 TMR • IEC 62443 • 0.05ms vs 3000ms+ • 6MB vs 135MB", false),
-        TerminalLine::with_link("", "     ", "Live Demo", "https://guardian-one-web-demo.vercel.app", ""),
-        TerminalLine::with_link("", " | ", "GitHub", "https://github.com/gammahazard/Guardian-one-web-demo", ""),
-        TerminalLine::text("", "", false),
-        TerminalLine::text("", divider, false),
-        TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  Edge WASI Runtime"),
-        TerminalLine::text("", "  └─ Secure IoT plugin host: Hot-swap Python on Raspberry Pi", false),
-        TerminalLine::text("", "     Capability security • <10ms reload • Real GPIO hardware", false),
-        TerminalLine::with_link("", "     ", "GitHub", "https://github.com/gammahazard/edge-wasi-runtime", ""),
-        TerminalLine::text("", "", false),
-        TerminalLine::text("", divider, false),
-        TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  ICS Guardian"),
-        TerminalLine::text("", "  └─ Capability-based security: WASI 0.2 data diode for ICS", false),
-        TerminalLine::text("", "     14.7KB WASM vs 500MB+ Docker • 17 security tests", false),
-        TerminalLine::with_link("", "     ", "Live Demo", "https://vanguard-ics-guardian.vercel.app", ""),
-        TerminalLine::with_link("", " | ", "GitHub", "https://github.com/gammahazard/vanguard-ics-guardian", ""),
-        TerminalLine::text("", "", false),
-        TerminalLine::text("", divider, false),
-        TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  Edge Protocol Demo"),
-        TerminalLine::text("", "  └─ Cloudflare Workers: URL shortener + rate limiter + KV", false),
-        TerminalLine::text("", "     Production patterns • CI/CD • 10M+ req/day capable", false),
-        TerminalLine::with_link("", "     ", "Live Demo", "https://edge-protocol-demo.pages.dev", ""),
-        TerminalLine::with_link("", " | ", "GitHub", "https://github.com/gammahazard/edge-protocol-demo", ""),
-        TerminalLine::text("", "  ───────────────────────────────────────────────────────────", false),
-        TerminalLine::text("", "", false),
-        // distributed systems and protocol work
-        TerminalLine::section("", "  // SYSTEMS ARCHITECTURE"),
-        TerminalLine::text("", "  ═══════════════════════════════════════════════════════════", false),
-        TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  Raft Consensus Cluster"),
-        TerminalLine::text("", "  └─ Distributed consensus: Same binary runs in browser + Pi", false),
-        TerminalLine::text("", "     120+ tests • PreVote protocol • Network partition handling", false),
-        TerminalLine::with_link("", "     ", "Live Demo", "https://raft-consensus.vercel.app", ""),
-        TerminalLine::with_link("", " | ", "GitHub", "https://github.com/gammahazard/Raft-Consensus", ""),
-        TerminalLine::text("", "", false),
-        TerminalLine::text("", divider, false),
-        TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  Protocol Gateway Sandbox"),
-        TerminalLine::text("", "  └─ Modbus → MQTT via WASM: Crash-contained translation", false),
-        TerminalLine::text("", "     ~7ms recovery • IEC 62443 attack minimization • 2oo3 TMR", false),
-        TerminalLine::with_link("", "     ", "Live Demo", "https://protocol-gateway-sandbox.vercel.app", ""),
-        TerminalLine::with_link("", " | ", "GitHub", "https://github.com/gammahazard/protocol-gateway-sandbox", ""),
-        TerminalLine::text("", "", false),
-        TerminalLine::text("", divider, false),
-        TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  Pacifica Engine"),
-        TerminalLine::text("", "  └─ HFT Bot: Sub-ms execution, 5-factor signals", false),
-        TerminalLine::with_link("", "     ", "GitHub", "https://github.com/gammahazard/auto-trade", ""),
-        TerminalLine::text("", "", false),
-        TerminalLine::text("", divider, false),
-        TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  Ore Supervisor"),
-        TerminalLine::text("", "  └─ macOS Daemon: Process health + auto-restart", false),
-        TerminalLine::with_link("", "     ", "GitHub", "https://github.com/gammahazard/ore-app-mac", ""),
-        TerminalLine::text("", "  ───────────────────────────────────────────────────────────", false),
-        TerminalLine::text("", "", false),
-        // production full-stack applications
-        TerminalLine::section("", "  // FULL STACK"),
-        TerminalLine::text("", "  ═══════════════════════════════════════════════════════════", false),
-        TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  Kennel Platform"),
-        TerminalLine::text("", "  └─ Flagship ERP: FIDO2 auth, RBAC, 61 endpoints", false),
-        TerminalLine::with_link("", "     ", "GitHub", "https://github.com/gammahazard/Vanguard-kennel-frontend", ""),
-        TerminalLine::with_link("", " | ", "Demo", "https://vanguard-frontend.vercel.app", ""),
-        TerminalLine::text("", "", false),
-        TerminalLine::text("", divider, false),
-        TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  CyberVerse Exchange"),
-        TerminalLine::text("", "  └─ Multi-chain bridge: SOL/ADA/ETH/ERGO wallets", false),
-        TerminalLine::with_link("", "     ", "GitHub", "https://github.com/gammahazard/CyberVerse-exchange", ""),
-        TerminalLine::text("", "  ───────────────────────────────────────────────────────────", false),
-        TerminalLine::text("", "", false),
-        // wasm technical demonstrations
-        TerminalLine::section("", "  // WASM & TOOLS"),
-        TerminalLine::text("", "  ═══════════════════════════════════════════════════════════", false),
-        TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  PokeFrame"),
-        TerminalLine::text("", "  └─ Rust GameBoy emulator → WASM, 60fps", false),
-        TerminalLine::with_link("", "     ", "GitHub", "https://github.com/gammahazard/PokeFramePublic", ""),
-        TerminalLine::text("", "", false),
-        TerminalLine::text("", divider, false),
+fn get_projects_output(projects: &[&Project]) -> Vec<TerminalLine> {
+    let mut lines = vec![TerminalLine::text("", "", false)];
+    let mut first_section = true;
+    for domain in Domain::ALL {
+        let projects: Vec<&Project> = projects.iter().copied().filter(|p| p.category == domain).collect();
+        if projects.is_empty() {
+            continue;
+        }
+        if !first_section {
+            lines.push(TerminalLine::rule("  ", "─"));
+            lines.push(TerminalLine::text("", "", false));
+        }
+        first_section = false;
+        lines.push(TerminalLine::section("", &format!("  // {}", domain.title())));
+        lines.push(TerminalLine::rule("  ", "═"));
+        lines.push(TerminalLine::text("", "", false));
+        for (i, project) in projects.iter().enumerate() {
+            if i > 0 {
+                lines.push(TerminalLine::text("", "", false));
+                lines.push(TerminalLine::rule("  ", "- "));
+                lines.push(TerminalLine::text("", "", false));
+            }
+            lines.extend(project_summary(project));
+        }
+    }
+    lines.push(TerminalLine::text("", "", false));
+    lines
+}
+
+// `projects -l` - one line per project, in the order given
+fn get_projects_compact(projects: &[&Project]) -> Vec<TerminalLine> {
+    let mut table = Table::new().indent("    ");
+    for p in projects {
+        let demo = if p.demo_url.is_some() { "▶" } else { "" };
+        table.push(vec![Cell::cmd(&p.title), demo.into(), p.category.slug().into(), p.description.as_str().into()]);
+    }
+    let mut lines = vec![TerminalLine::text("", "", false)];
+    lines.extend(table.render());
+    lines.push(TerminalLine::text("", "", false));
+    lines
+}
+
+// title, pitch, metrics and links - one entry of the `projects` listing
+fn project_summary(project: &Project) -> Vec<TerminalLine> {
+    let mut lines = vec![
+        TerminalLine::bold("", &format!("  {}", project.title)),
+        TerminalLine::paragraph("  └─ ", "     ", &project.description),
+    ];
+    if !project.metrics.is_empty() {
+        lines.push(TerminalLine::paragraph("     ", "     ", &project.metrics.join(" • ")));
+    }
+    lines.push(TerminalLine::links("", "     ", &project_links(project, "Live Demo", "GitHub"), " | "));
+    lines
+}
+
+fn project_links<'a>(project: &'a Project, demo: &'a str, github: &'a str) -> Vec<(&'a str, &'a str)> {
+    let mut links = vec![];
+    if let Some(url) = &project.demo_url {
+        links.push((demo, url.as_str()));
+    }
+    links.push((github, project.github_url.as_str()));
+    links
+}
+
+// `project <name>` - everything known about one project
+fn get_project_card(project: &Project, all: &[Project]) -> Vec<TerminalLine> {
+    let mut lines = vec![
         TerminalLine::text("", "", false),
-        TerminalLine::bold("", "  Fleet-SDK Builder"),
-        TerminalLine::text("", "  └─ Ergo transaction builder: EIP-12 compliant", false),
-        TerminalLine::with_link("", "     ", "GitHub", "https://github.com/gammahazard/Fleet-SDK-cc", ""),
-        TerminalLine::with_link("", " | ", "Demo", "https://gammahazard.github.io/Fleet-SDK-cc/dist/index.html", ""),
+        TerminalLine::bold("", &format!("  {}", project.title)),
+        TerminalLine::text("", &format!("  {}{}", project.category.title(), project.date.as_ref().map(|d| format!(" · {}", d)).unwrap_or_default()), false),
+        TerminalLine::rule("  ", "═"),
         TerminalLine::text("", "", false),
-    ]
+        TerminalLine::section("", "  > OVERVIEW"),
+        TerminalLine::paragraph("    ", "    ", &project.description),
+    ];
+    lines.extend(project.highlights.iter().map(|h| TerminalLine::paragraph("    ", "    ", h)));
+
+    let mut bullets = |title: &str, items: &[String]| {
+        if items.is_empty() {
+            return;
+        }
+        lines.push(TerminalLine::text("", "", false));
+        lines.push(TerminalLine::section("", &format!("  > {}", title)));
+        lines.extend(items.iter().map(|item| TerminalLine::paragraph("    • ", "      ", item)));
+    };
+    bullets("ARCHITECTURE", &project.architecture);
+    bullets("METRICS", &project.metrics);
+
+    if !project.tags.is_empty() {
+        lines.push(TerminalLine::text("", "", false));
+        lines.push(TerminalLine::section("", "  > TECH"));
+        lines.extend(project.tags.chunks(4).map(|row| {
+            TerminalLine::skill_badges("    ", &row.iter().map(String::as_str).collect::<Vec<_>>())
+        }));
+    }
+
+    lines.push(TerminalLine::text("", "", false));
+    lines.push(TerminalLine::section("", "  > LINKS"));
+    lines.push(TerminalLine::links("", "    ", &project_links(project, "Live Demo", "GitHub"), " | "));
+
+    // most shared tags first, same domain breaks ties
+    let mut related: Vec<(usize, bool, &Project)> = all
+        .iter()
+        .filter(|p| p.title != project.title)
+        .map(|p| (p.tags.iter().filter(|t| project.tags.contains(t)).count(), p.category == project.category, p))
+        .filter(|(shared, same_domain, _)| *shared > 0 || *same_domain)
+        .collect();
+    related.sort_by_key(|&(shared, same_domain, _)| std::cmp::Reverse((shared, same_domain)));
+    if !related.is_empty() {
+        let related: Vec<&Project> = related.into_iter().take(3).map(|(_, _, p)| p).collect();
+        lines.push(TerminalLine::text("", "", false));
+        lines.push(TerminalLine::section("", "  > RELATED"));
+        let mut table = Table::new().indent("      ").gap(3);
+        for p in related {
+            table.push(vec![Cell::cmd(&format!("project {}", p.slug())), p.title.as_str().into()]);
+        }
+        lines.extend(table.render());
+    }
+    lines.push(TerminalLine::text("", "", false));
+    lines
+}
+
+// level as ten cells, e.g. 8 -> ████████░░
+fn skill_bar(skill: &Skill) -> String {
+    match skill.level {
+        Some(level) => format!("{}{}", "█".repeat(level as usize), "░".repeat(10 - level as usize)),
+        // not rated yet - a dotted track rather than a bar that reads as 0
+        None => "·".repeat(10),
+    }
+}
+
+fn skill_years(skill: &Skill) -> String {
+    skill.years.map_or_else(|| "-".to_string(), |years| format!("{}y", years))
+}
+
+// name, bar and years - years right-aligned so 3y and 12y end together
+fn skill_chart(skills: &[&Skill]) -> Table {
+    let mut table = Table::new().align(2, Align::Right);
+    for s in skills {
+        table.push(vec![s.name.as_str().into(), skill_bar(s).into(), skill_years(s).into()]);
+    }
+    table
 }
 
 // technical skills - organized by specialty area
 // emphasizes wasi 0.2 and industrial edge expertise
-fn get_skills_output() -> Vec<TerminalLine> {
-    vec![
-        TerminalLine::text("", "", false),
-        TerminalLine::section("", "  // WASI & EDGE COMPUTING"),
-        TerminalLine::badges("  ", vec!["WASI 0.2", "Component Model", "Wasmtime", "Leptos"]),
-        TerminalLine::badges("  ", vec!["Hot Module Reloading", "Capability Security", "JCO"]),
-        TerminalLine::text("", "", false),
-        TerminalLine::section("", "  // SYSTEMS & PROTOCOLS"),
-        TerminalLine::badges("  ", vec!["Rust", "C++", "C", "Distributed Systems"]),
-        TerminalLine::badges("  ", vec!["Raft Consensus", "IEC 62443", "Modbus TCP", "MQTT"]),
+fn get_skills_output(graph: bool, by_level: bool) -> Vec<TerminalLine> {
+    let portfolio = portfolio();
+    // one name column for every chart so the bars line up across sections
+    let width = portfolio.skills.iter().flat_map(|a| &a.items).map(|s| table::width(&s.name)).max().unwrap_or(0);
+    let mut lines = vec![TerminalLine::text("", "", false)];
+    if graph && portfolio.skills.iter().flat_map(|a| &a.items).all(|s| s.level.is_none()) {
+        lines.push(TerminalLine::text("", "  no skill levels in content/portfolio.toml yet", false));
+        lines.push(TerminalLine::text("", "", false));
+    }
+    for area in &portfolio.skills {
+        let mut skills: Vec<&Skill> = area.items.iter().collect();
+        if by_level {
+            skills.sort_by_key(|s| std::cmp::Reverse((s.level, s.years)));
+        }
+        lines.push(TerminalLine::section("", &format!("  // {}", area.area)));
+        if graph {
+            lines.extend(skill_chart(&skills).min_width(0, width).render());
+        } else {
+            lines.extend(skills.chunks(4).map(|row| TerminalLine::badges("  ", row.iter().map(|s| s.name.as_str()).collect())));
+        }
+        lines.push(TerminalLine::text("", "", false));
+    }
+    lines
+}
+
+// one area as a bar chart - what `cat skills/<area>.md` shows
+fn get_skill_area_output(area: &SkillArea) -> Vec<TerminalLine> {
+    let mut lines = vec![TerminalLine::text("", "", false), TerminalLine::section("", &format!("  // {}", area.area))];
+    lines.extend(skill_chart(&area.items.iter().collect::<Vec<_>>()).render());
+    lines.push(TerminalLine::text("", "", false));
+    lines
+}
+
+// bar and years where the content has them, then the area
+fn skill_summary(area: &SkillArea, skill: &Skill) -> String {
+    let mut parts = vec![];
+    if let Some(level) = skill.level {
+        parts.push(format!("{} {}/10", skill_bar(skill), level));
+    }
+    if let Some(years) = skill.years {
+        parts.push(format!("{}y", years));
+    }
+    parts.push(area.area.clone());
+    parts.join(" · ")
+}
+
+// `skills <name>` - one skill and every project that used it
+fn get_skill_usage(area: &SkillArea, skill: &Skill, projects: &[Project]) -> Vec<TerminalLine> {
+    let mut lines = vec![
         TerminalLine::text("", "", false),
-        TerminalLine::section("", "  // EMBEDDED & HARDWARE"),
-        TerminalLine::badges("  ", vec!["Raspberry Pi", "GPIO", "Embedded Linux"]),
-        TerminalLine::badges("  ", vec!["2oo3 TMR", "Industrial Control", "DHT22 Sensors"]),
+        TerminalLine::bold("", &format!("  {}", skill.name)),
+        TerminalLine::text("", &format!("  {}", skill_summary(area, skill)), false),
         TerminalLine::text("", "", false),
-        TerminalLine::section("", "  // FULL STACK"),
-        TerminalLine::badges("  ", vec!["TypeScript", "Next.js 14+", "React", "Node.js"]),
-        TerminalLine::badges("  ", vec!["Tailwind CSS", "GraphQL", "gRPC"]),
+    ];
+    let used: Vec<&Project> = projects.iter().filter(|p| p.tags.contains(&skill.name)).collect();
+    if used.is_empty() {
+        lines.push(TerminalLine::text("", "  not tagged on any listed project yet", false));
+    } else {
+        lines.push(TerminalLine::section("", &format!("  > USED IN ({})", used.len())));
+        for project in used {
+            lines.push(TerminalLine::help_entry("    ", &project.title, &format!("  {}", project.description)));
+            lines.push(TerminalLine::links("", "      ", &project_links(project, "Live Demo", "GitHub"), " | "));
+        }
+    }
+    lines.push(TerminalLine::text("", "", false));
+    lines
+}
+
+// profile and flagship work
+fn get_about_output() -> Vec<TerminalLine> {
+    let portfolio = portfolio();
+    let profile = &portfolio.profile;
+    let mut lines = vec![
         TerminalLine::text("", "", false),
-        TerminalLine::section("", "  // INFRASTRUCTURE & DATA"),
-        TerminalLine::badges("  ", vec!["Docker", "AWS", "PostgreSQL", "MongoDB"]),
-        TerminalLine::badges("  ", vec!["Redis", "Supabase", "CI/CD"]),
+        TerminalLine::bold("", &format!("  {}", profile.name)),
+        TerminalLine::text("", &format!("  {}", profile.title), false),
         TerminalLine::text("", "", false),
-        TerminalLine::section("", "  // SECURITY & WEB3"),
-        TerminalLine::badges("  ", vec!["FIDO2/WebAuthn", "OAuth 2.0", "JWT"]),
-        TerminalLine::badges("  ", vec!["Zero Trust", "Cloud Identity", "WebSockets"]),
+        TerminalLine::rule("  ", "━"),
         TerminalLine::text("", "", false),
-    ]
+        TerminalLine::section("", "  > ENGINEERING FOCUS"),
+    ];
+    lines.extend(profile.focus.iter().map(|line| TerminalLine::paragraph("    ", "    ", line)));
+    lines.push(TerminalLine::text("", "", false));
+    lines.push(TerminalLine::section("", "  > FLAGSHIP WORK"));
+    for project in portfolio.projects.iter().filter(|p| p.flagship) {
+        lines.push(TerminalLine::bold("", &format!("    {}", project.title)));
+        if let Some(highlight) = project.highlights.first() {
+            lines.push(TerminalLine::paragraph("    └─ ", "       ", highlight));
+        }
+        lines.push(TerminalLine::links("", "       ", &project_links(project, "[Demo]", "[GitHub]"), " "));
+        lines.push(TerminalLine::text("", "", false));
+    }
+    lines.push(TerminalLine::section("", "  > CORE EXPERTISE"));
+    lines.extend(profile.expertise.iter().map(|item| TerminalLine::paragraph("    • ", "      ", item)));
+    lines.push(TerminalLine::text("", "", false));
+    lines
 }
 
 // contact info
 fn get_contact_output() -> Vec<TerminalLine> {
-    vec![
-        TerminalLine::text("", "", false),
-        TerminalLine::with_link("", "  Email   ", "cm.mongo.web3@gmail.com", "mailto:cm.mongo.web3@gmail.com", ""),
-        TerminalLine::with_link("", "  GitHub  ", "gammahazard", "https://github.com/gammahazard", ""),
-        TerminalLine::text("", "", false),
-    ]
+    let portfolio = portfolio();
+    let contact = &portfolio.contact;
+    let mut lines = vec![TerminalLine::text("", "", false)];
+    lines.extend(
+        Table::new()
+            .border(Border::Rounded)
+            .row(vec![Cell::bold("Email"), Cell::link(&contact.email, &format!("mailto:{}", contact.email))])
+            .row(vec![Cell::bold("GitHub"), Cell::link(&contact.github, &contact.github_url())])
+            .render(),
+    );
+    lines.push(TerminalLine::text("", "", false));
+    lines
 }
 
 #[component]
 fn Terminal() -> impl IntoView {
-    let (history, set_history) = create_signal::<Vec<TerminalLine>>(vec![]);
+    let (history, set_history) = create_signal(Scrollback::load());
     let (current_input, set_current_input) = create_signal(String::new());
     let (show_input, set_show_input) = create_signal(false);
+    // exit status of the last command line, `$?`
+    let (last_status, set_last_status) = create_signal(0);
     let input_ref = create_node_ref::<Input>();
     let terminal_body_ref = create_node_ref::<Div>();
+    let history_ref = create_node_ref::<Div>();
+    let registry = store_value(Registry::builtin());
+    // typed commands for Up/Down and `history`, separate from the rendered output
+    let command_history = store_value(CommandHistory::load());
+    // set after a Tab that found several matches, a second Tab lists them
+    let tab_pending = store_value(false);
+    // the numbered list the last ambiguous `open` printed, for `open N`
+    let open_choices = store_value(Vec::<(String, String)>::new());
+    // working directory in the virtual filesystem, shown in the prompt and title
+    let cwd = create_rw_signal(vfs::HOME.to_string());
+    // Ctrl+R reverse incremental search, None when not searching
+    let (search, set_search) = create_signal::<Option<ReverseSearch>>(None);
+    // width of the terminal in output columns - dividers and paragraphs
+    // already on screen redraw when it changes
+    let columns = create_rw_signal(screen::columns());
     
     // Track uptime
     let start_time = js_sys::Date::now();
 
+    // pick up the published portfolio.json if the build names one - failures
+    // just leave the built-in content in place, `sync` reports them
+    if let Some(url) = content::REMOTE_URL {
+        spawn_local(async move {
+            let _ = content::refresh(url).await;
+        });
+    }
+
+    // only the lines around the viewport are mounted, spacers stand in for
+    // the rest - see scrollback.rs
+    let scroll_top = create_rw_signal(0.0);
+    let viewport = create_rw_signal(0.0);
+    let visible = create_memo(move |_| history.with(|h| h.window(scroll_top.get(), viewport.get())));
+    // where the view stays while fresh output is measured, None once the
+    // layout has settled or the visitor scrolls themselves
+    let anchor = store_value(None::<Anchor>);
+
+    let snap = move || {
+        let (Some(body), Some(list), Some(anchor)) = (terminal_body_ref.get_untracked(), history_ref.get_untracked(), anchor.get_value()) else {
+            return;
+        };
+        let top = match anchor {
+            Anchor::Bottom => body.scroll_height() as f64,
+            Anchor::Line(index) => {
+                let list_top = list.get_bounding_client_rect().top() - body.get_bounding_client_rect().top() + body.scroll_top() as f64;
+                list_top + history.with_untracked(|h| h.offset(index))
+            }
+        };
+        body.set_scroll_top(top as i32);
+        scroll_top.set(body.scroll_top() as f64);
+    };
+
     // Auto-scroll logic
+    let newest = create_memo(move |_| history.with(|h| h.lines().back().map(|line| line.id)));
     create_effect(move |_| {
-        let _ = history.get();
-        if let Some(el) = terminal_body_ref.get() {
-            set_timeout(move || {
-                let window_width = window().inner_width().ok().and_then(|w| w.as_f64()).unwrap_or(1024.0);
-                if window_width < 768.0 {
-                    // On mobile, try to scroll the last user command to the top
-                    let commands = document().get_elements_by_class_name("user-command");
-                    if commands.length() > 0 {
-                        let last_cmd = commands.item(commands.length() - 1).unwrap();
-                        last_cmd.scroll_into_view_with_bool(true); // true = align to top
-                    } else {
-                        let _ = el.set_scroll_top(el.scroll_height());
+        newest.track();
+        let window_width = window().inner_width().ok().and_then(|w| w.as_f64()).unwrap_or(1024.0);
+        anchor.set_value(Some(if window_width < 768.0 {
+            // On mobile, try to scroll the last user command to the top
+            history.with_untracked(|h| h.last_command()).map_or(Anchor::Bottom, Anchor::Line)
+        } else {
+            // On desktop, standard console behavior (scroll to bottom)
+            Anchor::Bottom
+        }));
+        set_timeout(snap, std::time::Duration::from_millis(10));
+    });
+
+    // measure the mounted lines once they are drawn - a line's height is the
+    // distance to the element after it, so margins count. the anchor is let
+    // go when nothing changed
+    create_effect(move |_| {
+        let window = visible.get();
+        request_animation_frame(move || {
+            let Some(list) = history_ref.get_untracked() else {
+                return;
+            };
+            let children = list.children();
+            let elements: Vec<web_sys::Element> = (0..children.length()).filter_map(|i| children.item(i)).collect();
+            let tops: Vec<f64> = elements.iter().map(|el| el.get_bounding_client_rect().top()).collect();
+            let changed = set_history.try_update_untracked(|h| {
+                let mut changed = false;
+                // [top spacer, lines.., bottom spacer]
+                for (k, el) in elements.iter().enumerate().skip(1).take(elements.len().saturating_sub(2)) {
+                    let index = window.start + k - 1;
+                    let id = el.get_attribute("data-line").and_then(|id| id.parse().ok());
+                    if h.lines().get(index).map(|line| line.id) == id {
+                        changed |= h.set_height(index, tops[k + 1] - tops[k]);
                     }
-                } else {
-                    // On desktop, standard console behavior (scroll to bottom)
-                    let _ = el.set_scroll_top(el.scroll_height());
                 }
-            }, std::time::Duration::from_millis(10));
-        }
+                changed
+            });
+            if changed == Some(true) {
+                set_history.update(|_| {});
+                snap();
+            } else {
+                anchor.set_value(None);
+            }
+        });
     });
 
-    // boot animation
+    // measure once the body is mounted, then again on every resize (also
+    // catches the smaller mobile font kicking in)
+    let remeasure = move || {
+        if let Some(body) = terminal_body_ref.get_untracked() {
+            viewport.set(body.client_height() as f64);
+            if screen::remeasure(&body) {
+                columns.set(screen::columns());
+                set_history.update(Scrollback::forget_heights);
+            }
+        }
+    };
     create_effect(move |_| {
-        let boot_lines = get_boot_sequence();
-        for (delay, line) in boot_lines {
-            let line_clone = line.clone();
-            set_timeout(
-                move || {
-                    set_history.update(|h| h.push(line_clone.clone()));
-                },
-                std::time::Duration::from_millis(delay),
-            );
+        if terminal_body_ref.get().is_some() {
+            remeasure();
+        }
+    });
+    window_event_listener(ev::resize, move |_| remeasure());
+
+    // boot animation - any key or a click skips to the prompt. keys typed
+    // into the prompt are left alone, or the Enter that ran `reboot` would
+    // skip the replay it started
+    let boot_timeline = Timeline::new();
+    registry.with_value(|registry| boot(registry, boot_timeline, set_history, set_show_input));
+    window_event_listener(ev::keydown, move |ev| {
+        let typed = ev.target().and_then(|t| t.dyn_into::<web_sys::Element>().ok()).is_some_and(|el| el.tag_name() == "INPUT");
+        if !typed && boot_timeline.is_playing() {
+            boot_timeline.finish();
         }
-        set_timeout(
-            move || {
-                set_show_input.set(true);
-            },
-            std::time::Duration::from_millis(2700),
-        );
     });
 
     let focus_input = move |_| {
+        if boot_timeline.is_playing() {
+            boot_timeline.finish();
+        }
         if let Some(input) = input_ref.get() {
             let _ = input.focus();
         }
     };
 
     let process_command = move |cmd: String| {
-        let cmd_lower = cmd.to_lowercase().trim().to_string();
-        
-        // add command to history
-        set_history.update(|h| {
-            h.push(TerminalLine { 
-                id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-                prefix: "λ".to_string(), 
-                parts: vec![OutputPart::Text(format!(" {}", cmd))],
-                is_command: true,
-                is_boot: false,
-            });
-        });
-
-        // process
-        let responses: Vec<TerminalLine> = match cmd_lower.as_str() {
-            "help" | "h" | "?" => vec![
-                TerminalLine::text("", "", false),
-                TerminalLine::section("", "  > AVAILABLE COMMANDS"),
-                TerminalLine::text("", "", false),
-                TerminalLine {
-                    id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-                    prefix: "".to_string(),
-                    parts: vec![
-                        OutputPart::Bold("  projects".to_string()),
-                        OutputPart::Text("     List all portfolio projects".to_string()),
-                    ],
-                    is_command: false,
-                    is_boot: false,
-                },
-                TerminalLine {
-                    id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-                    prefix: "".to_string(),
-                    parts: vec![
-                        OutputPart::Bold("  skills".to_string()),
-                        OutputPart::Text("       View technical stack and expertise".to_string()),
-                    ],
-                    is_command: false,
-                    is_boot: false,
-                },
-                TerminalLine {
-                    id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-                    prefix: "".to_string(),
-                    parts: vec![
-                        OutputPart::Bold("  about".to_string()),
-                        OutputPart::Text("        Professional profile and bio".to_string()),
-                    ],
-                    is_command: false,
-                    is_boot: false,
-                },
-                TerminalLine {
-                    id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-                    prefix: "".to_string(),
-                    parts: vec![
-                        OutputPart::Bold("  contact".to_string()),
-                        OutputPart::Text("      Get in touch".to_string()),
-                    ],
-                    is_command: false,
-                    is_boot: false,
-                },
-                TerminalLine {
-                    id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-                    prefix: "".to_string(),
-                    parts: vec![
-                        OutputPart::Bold("  clear".to_string()),
-                        OutputPart::Text("        Reset terminal".to_string()),
-                    ],
-                    is_command: false,
-                    is_boot: false,
-                },
-                TerminalLine::text("", "", false),
-                TerminalLine::section("", "  > EASTER EGGS"),
-                TerminalLine::text("", "  Try: neofetch, whoami, ls, sudo hire me, ping, date", false),
-                TerminalLine::text("", "", false),
-            ],
-            "projects" | "ls projects" | "repos" => get_projects_output(),
-            "skills" | "stack" | "tech" => get_skills_output(),
-            "contact" | "email" => get_contact_output(),
-            "about" => vec![
-                TerminalLine::text("", "", false),
-                TerminalLine::bold("", "  CM Mongo"),
-                TerminalLine::text("", "  Systems Engineer | Edge Computing Specialist", false),
-                TerminalLine::text("", "", false),
-                TerminalLine::text("", "  ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━", false),
-                TerminalLine::text("", "", false),
-                TerminalLine::section("", "  > ENGINEERING FOCUS"),
-                TerminalLine::text("", "    Building crash-contained edge infrastructure with Rust & WASI 0.2.", false),
-                TerminalLine::text("", "    Specializing in capability-based security for industrial control systems.", false),
-                TerminalLine::text("", "", false),
-                TerminalLine::section("", "  > FLAGSHIP WORK"),
-                TerminalLine::bold("", "    Guardian One"),
-                TerminalLine::text("", "    └─ Industrial edge platform (100,000x performance vs Python)", false),
-                TerminalLine::with_link("", "       ", "[Demo]", "https://guardian-one-web-demo.vercel.app", ""),
-                TerminalLine::with_link("", " ", "[GitHub]", "https://github.com/gammahazard/Guardian-one-web-demo", ""),
-                TerminalLine::text("", "", false),
-                TerminalLine::bold("", "    Edge WASI Runtime"),
-                TerminalLine::text("", "    └─ Hot-swappable IoT plugins on Raspberry Pi bare metal", false),
-                TerminalLine::with_link("", "       ", "[GitHub]", "https://github.com/gammahazard/edge-wasi-runtime", ""),
-                TerminalLine::text("", "", false),
-                TerminalLine::bold("", "    Raft Consensus"),
-                TerminalLine::text("", "    └─ Distributed systems running in browser + hardware", false),
-                TerminalLine::with_link("", "       ", "[Demo]", "https://raft-consensus.vercel.app", ""),
-                TerminalLine::with_link("", " ", "[GitHub]", "https://github.com/gammahazard/Raft-Consensus", ""),
-                TerminalLine::text("", "", false),
-                TerminalLine::section("", "  > CORE EXPERTISE"),
-                TerminalLine::text("", "    • WASI 0.2 Component Model", false),
-                TerminalLine::text("", "    • IEC 62443 Industrial Security", false),
-                TerminalLine::text("", "    • Distributed Consensus (Raft)", false),
-                TerminalLine::text("", "    • Hot Module Reloading", false),
-                TerminalLine::text("", "    • Zero-Liability Architecture", false),
-                TerminalLine::text("", "", false),
-            ],
-            "clear" | "cls" => {
-                set_history.set(vec![
-                    TerminalLine::text("[ok]", " cleared.", true),
-                ]);
-                set_current_input.set(String::new());
-                return;
-            },
-            // easter eggs
-            "ls" | "ls -la" => vec![
-                TerminalLine::text("", "", false),
-                TerminalLine::text("", "  drwxr-xr-x  kennel-platform/", false),
-                TerminalLine::text("", "  drwxr-xr-x  auto-trade/", false),
-                TerminalLine::text("", "  drwxr-xr-x  CyberVerse-exchange/", false),
-                TerminalLine::text("", "  drwxr-xr-x  Fleet-SDK-cc/", false),
-                TerminalLine::text("", "  drwxr-xr-x  PokeFramePublic/", false),
-                TerminalLine::text("", "  -rw-r--r--  .env [redacted]", false),
-                TerminalLine::text("", "", false),
-            ],
-            "neofetch" => {
-                let now = js_sys::Date::now();
-                let uptime_ms = now - start_time;
-                let minutes = (uptime_ms / 60000.0).floor();
-                let seconds = ((uptime_ms % 60000.0) / 1000.0).floor();
-                
-                vec![
-                    TerminalLine::text("", "", false),
-                    TerminalLine::text("", "        /\\         cm_mongo@vanguard", false),
-                    TerminalLine::text("", "       /  \\        os: vanguardos 2.0", false),
-                    TerminalLine::text("", "      / 🦀 \\       kernel: rust + wasm", false),
-                    TerminalLine::text("", "     /______\\      shell: leptos", false),
-                    TerminalLine::text("", &format!("                   uptime: {}m {}s", minutes, seconds), false),
-                    TerminalLine::text("", "", false),
-                ]
-            },
-            "whoami" => vec![TerminalLine::text("", "  vanguard", false)],
-            "pwd" => vec![TerminalLine::text("", "  /home/vanguard/portfolio", false)],
-            "uptime" => vec![TerminalLine::text("", "  up 1337 days, building systems", false)],
-            "sudo hire me" | "hire" => vec![
-                TerminalLine::text("", "", false),
-                TerminalLine::text("", "  [sudo] password for visitor: **********", false),
-                TerminalLine::text("", "  authenticating...", false),
-                TerminalLine::text("", "  ACCESS GRANTED.", false),
-                TerminalLine::text("", "", false),
-                TerminalLine::text("", "  initiating onboarding_sequence.sh...", false),
-                TerminalLine::text("", "  > Priority connection established.", false),
-                TerminalLine::text("", "  > Ready to architect your next system.", false),
-                TerminalLine::text("", "", false),
-                TerminalLine::with_link("", "  [ EXECUTE OFFER ] ", "Send Email", "mailto:cm.mongo.web3@gmail.com?subject=Priority%20Job%20Offer", ""),
-                TerminalLine::text("", "", false),
-            ],
-            "sudo" => vec![TerminalLine::text("", "  try: sudo hire me", false)],
-            "exit" | "quit" => vec![TerminalLine::text("", "  no escape. type 'help' :)", false)],
-            "rm" | "rm -rf" | "rm -rf /" => vec![TerminalLine::text("", "  🛑 nice try.", false)],
-            "rust" | "🦀" => vec![TerminalLine::text("", "  🦀 btw i use rust", false)],
-            "ping" => {
-                let set_history = set_history.clone();
-                spawn_local(async move {
-                    let start = js_sys::Date::now();
-                    // Fetch current page just to measure round trip to origin/cache
-                    let _ = gloo_net::http::Request::get("/").send().await;
-                    let end = js_sys::Date::now();
-                    let duration = end - start;
-                    
-                    set_history.update(|h| {
-                        h.push(TerminalLine::text("", &format!("  pong. {:.0}ms", duration), false));
-                    });
-                });
-                vec![TerminalLine::text("", "  pinging...", false)]
-            },
-            "date" => vec![TerminalLine::text("", "  2026. building the future.", false)],
-            "cat readme" | "cat readme.md" => vec![
-                TerminalLine::text("", "", false),
-                TerminalLine::text("", "  # vanguard secure solutions", false),
-                TerminalLine::text("", "  > engineering complete systems", false),
-                TerminalLine::text("", "", false),
-            ],
-            "" => vec![],
-            _ => {
-                let valid_commands = vec![
-                    "about", "clear", "contact", "help", "neofetch", 
-                    "projects", "skills", "whoami", "ls", "sudo", "date", "ping"
-                ];
-                
-                let mut closest_cmd = "";
-                let mut min_dist = usize::MAX;
-
-                for valid in valid_commands {
-                    let dist = levenshtein(&cmd_lower, valid);
-                    if dist < min_dist {
-                        min_dist = dist;
-                        closest_cmd = valid;
-                    }
-                }
+        let echo_command = move |line: &str| set_history.update(|h| h.push(TerminalLine::command(line)));
 
-                if min_dist <= 2 {
-                    vec![
-                        TerminalLine::text("", &format!("  command not found: {}", cmd), false),
-                        TerminalLine::text("", &format!("  did you mean '{}'? (dist: {})", closest_cmd, min_dist), false),
-                        TerminalLine::text("", "  type 'help' for commands", false),
-                    ]
-                } else {
-                    vec![
-                        TerminalLine::text("", &format!("  command not found: {}", cmd), false),
-                        TerminalLine::text("", "  type 'help' for commands", false),
-                    ]
+        // history expansion (`!!`, `!n`) comes first and the expanded line is
+        // what gets echoed and recorded, like bash
+        let output = match command_history.with_value(|h| h.expand(&cmd)) {
+            Ok(line) => {
+                command_history.update_value(|h| h.push(&line));
+                echo_command(&line);
+                match tokenize(&line).and_then(parse) {
+                    Ok(pipelines) => registry.with_value(|registry| {
+                        let ctx = Context { registry, scrollback: history, set_history, start_time, history: command_history, open_choices, cwd, stdin: None };
+                        registry.execute_line(&pipelines, last_status.get_untracked(), &ctx)
+                    }),
+                    Err(err) => Output::error(2, &format!("parse error: {}", err)),
                 }
-            },
+            }
+            Err(err) => {
+                echo_command(&cmd);
+                Output::error(1, &err)
+            }
         };
+        set_last_status.set(output.status);
 
-        for line in responses {
-            set_history.update(|h| h.push(line));
+        if output.clear {
+            // boot lines still on their way would land after the clear
+            boot_timeline.cancel();
+        }
+        set_history.update(|h| {
+            if output.clear {
+                h.clear();
+            }
+            h.extend(output.lines);
+        });
+        if output.reboot {
+            registry.with_value(|registry| boot(registry, boot_timeline, set_history, set_show_input));
         }
         set_current_input.set(String::new());
     };
@@ -676,9 +870,164 @@ fn Terminal() -> impl IntoView {
         }
     };
 
+    // apply a readline edit to the input, keeping the caret where the edit put it
+    let edit_line = move |edit: fn(&mut EditLine)| {
+        let Some(input) = input_ref.get_untracked() else {
+            return;
+        };
+        let caret = input.selection_start().ok().flatten().unwrap_or(u32::MAX);
+        let mut line = EditLine::from_dom(&input.value(), caret);
+        edit(&mut line);
+        set_current_input.set(line.text());
+        let caret = line.caret_utf16();
+        let _ = input.set_selection_range(caret, caret);
+    };
+
+    // Ctrl+C - drop the line, echoing it with ^C like a real terminal
+    let abandon_line = move |line: String| {
+        set_history.update(|h| h.push(TerminalLine::command(&format!("{}^C", line))));
+        set_current_input.set(String::new());
+        set_last_status.set(130);
+        command_history.update_value(|h| h.stop_browsing());
+    };
+
+    // keys while in (reverse-i-search) mode - printable keys edit the query,
+    // anything else leaves the mode with the match on the line
+    let on_search_keydown = move |ev: &web_sys::KeyboardEvent, mut state: ReverseSearch| {
+        let key = ev.key();
+        let plain = !ev.ctrl_key() && !ev.meta_key() && !ev.alt_key();
+        let cancel = key == "Escape" || (ev.ctrl_key() && key.eq_ignore_ascii_case("g"));
+        match key.as_str() {
+            _ if cancel => {
+                ev.prevent_default();
+                set_current_input.set(state.original);
+                set_search.set(None);
+                return;
+            }
+            "r" | "R" if ev.ctrl_key() => {
+                ev.prevent_default();
+                command_history.with_value(|h| state.next(h));
+            }
+            "c" | "C" if ev.ctrl_key() => {
+                ev.prevent_default();
+                set_search.set(None);
+                abandon_line(current_input.get_untracked());
+                return;
+            }
+            "Enter" => {
+                set_search.set(None);
+                handle_submit();
+                return;
+            }
+            "Backspace" => {
+                ev.prevent_default();
+                let mut query = state.query.clone();
+                query.pop();
+                command_history.with_value(|h| state.set_query(h, query));
+            }
+            "Shift" | "Control" | "Alt" | "Meta" => return,
+            k if plain && k.chars().count() == 1 => {
+                ev.prevent_default();
+                let query = format!("{}{}", state.query, k);
+                command_history.with_value(|h| state.set_query(h, query));
+            }
+            _ => {
+                set_search.set(None);
+                return;
+            }
+        }
+        if let Some(line) = command_history.with_value(|h| state.matched(h)) {
+            set_current_input.set(line);
+        }
+        set_search.set(Some(state));
+    };
+
     let on_keydown = move |ev: web_sys::KeyboardEvent| {
-        if ev.key() == "Enter" {
-            handle_submit();
+        if let Some(state) = search.get_untracked() {
+            on_search_keydown(&ev, state);
+            return;
+        }
+        let key = ev.key();
+        if key != "Tab" {
+            tab_pending.set_value(false);
+        }
+
+        // readline movement and kill keys - matched on the physical key so
+        // Alt+B/F work on layouts where Alt produces a symbol
+        let edit: Option<fn(&mut EditLine)> = match (ev.ctrl_key(), ev.alt_key(), ev.code().as_str()) {
+            (true, false, "KeyA") => Some(EditLine::start_of_line),
+            (true, false, "KeyE") => Some(EditLine::end_of_line),
+            (true, false, "KeyU") => Some(EditLine::kill_to_start),
+            (true, false, "KeyK") => Some(EditLine::kill_to_end),
+            // browsers may keep Ctrl+W for closing the tab
+            (true, false, "KeyW") => Some(EditLine::kill_word_back),
+            (false, true, "KeyB") => Some(EditLine::word_back),
+            (false, true, "KeyF") => Some(EditLine::word_forward),
+            _ => None,
+        };
+        if let Some(edit) = edit {
+            ev.prevent_default();
+            edit_line(edit);
+            return;
+        }
+
+        match key.as_str() {
+            // clear the screen but keep what is being typed
+            "l" | "L" if ev.ctrl_key() => {
+                ev.prevent_default();
+                set_history.update(Scrollback::clear);
+            }
+            "c" | "C" if ev.ctrl_key() => {
+                // leave a real selection alone so copying still works
+                let selected = input_ref.get_untracked().is_some_and(|input| {
+                    input.selection_start().ok().flatten() != input.selection_end().ok().flatten()
+                });
+                if !selected {
+                    ev.prevent_default();
+                    abandon_line(current_input.get_untracked());
+                }
+            }
+            // enter (reverse-i-search) - also keeps the browser from reloading
+            "r" | "R" if ev.ctrl_key() => {
+                ev.prevent_default();
+                set_search.set(Some(ReverseSearch::start(current_input.get_untracked())));
+                set_current_input.set(String::new());
+            }
+            "Enter" => handle_submit(),
+            // walk the command history, keeping the caret where the browser would not
+            "ArrowUp" => {
+                ev.prevent_default();
+                let current = current_input.get_untracked();
+                if let Some(line) = command_history.try_update_value(|h| h.prev(&current)).flatten() {
+                    set_current_input.set(line);
+                }
+            }
+            "ArrowDown" => {
+                ev.prevent_default();
+                if let Some(line) = command_history.try_update_value(|h| h.next()).flatten() {
+                    set_current_input.set(line);
+                }
+            }
+            // complete instead of leaving the terminal
+            "Tab" => {
+                ev.prevent_default();
+                let current = current_input.get_untracked();
+                let completion = registry.with_value(|registry| complete(registry, &current, &cwd.get_untracked()));
+                if completion.line != current {
+                    set_current_input.set(completion.line);
+                    tab_pending.set_value(false);
+                } else if completion.candidates.len() > 1 {
+                    if tab_pending.get_value() {
+                        // bash-style listing: the line so far, then every candidate
+                        set_history.update(|h| {
+                            h.push(TerminalLine::command(&current));
+                            h.push(TerminalLine::text("", &format!("  {}", completion.candidates.join("  ")), false));
+                        });
+                    }
+                    tab_pending.set_value(true);
+                }
+            }
+            _ => {}
         }
     };
 
@@ -691,16 +1040,33 @@ fn Terminal() -> impl IntoView {
                         <span class="btn-min"></span>
                         <span class="btn-max"></span>
                     </div>
-                    <span class="terminal-title">"vanguard@portfolio:~"</span>
+                    <span class="terminal-title">{move || format!("vanguard@portfolio:{}", vfs::display(&cwd.get()))}</span>
                 </div>
-                <div class="terminal-body" node_ref=terminal_body_ref>
-                    <div class="terminal-history">
+                <div
+                    class="terminal-body"
+                    node_ref=terminal_body_ref
+                    on:scroll=move |_| {
+                        if let Some(body) = terminal_body_ref.get_untracked() {
+                            scroll_top.set(body.scroll_top() as f64);
+                        }
+                    }
+                    on:wheel=move |_| anchor.set_value(None)
+                    on:touchstart=move |_| anchor.set_value(None)
+                >
+                    <div class="terminal-history" node_ref=history_ref>
+                        <div class="scroll-spacer" style:height=move || format!("{}px", visible.get().before)></div>
                         <For
-                            each=move || history.get()
+                            each=move || {
+                                let window = visible.get();
+                                history.with(|h| {
+                                    let end = window.end.min(h.len());
+                                    h.lines().range(window.start.min(end)..end).cloned().collect::<Vec<_>>()
+                                })
+                            }
                             key=|line| line.id
                             children=move |line| {
                                 view! {
-                                    <div class="terminal-line" class:boot-line=line.is_boot class:user-command=line.is_command>
+                                    <div class="terminal-line" data-line=line.id class:boot-line=line.is_boot class:user-command=line.is_command>
                                         {if !line.prefix.is_empty() {
                                             Some(view! { 
                                                 <span class=if line.is_command { "prompt" } else if line.is_boot { "boot-prefix" } else { "" }>
@@ -710,7 +1076,9 @@ fn Terminal() -> impl IntoView {
                                         } else {
                                             None
                                         }}
-                                        {line.parts.iter().map(|part| {
+                                        {line.parts.iter().enumerate().map(|(i, part)| {
+                                            // columns taken by the parts before this one
+                                            let lead = || line.parts[..i].iter().map(|p| table::width(p.text())).sum::<usize>();
                                             match part {
                                                 OutputPart::Text(text) => view! {
                                                     <span class=if line.is_command { "command" } else { "output" }>
@@ -732,11 +1100,49 @@ fn Terminal() -> impl IntoView {
                                                         {text.clone()}
                                                     </span>
                                                 }.into_view(),
+                                                OutputPart::CmdBadge { text, command } => {
+                                                    let command = command.clone();
+                                                    view! {
+                                                        <button class="skill-badge skill-link" title=command.clone() on:click=move |_| process_command(command.clone())>
+                                                            {text.clone()}
+                                                        </button>
+                                                    }.into_view()
+                                                }
                                                 OutputPart::CmdName(text) => view! {
                                                     <span class="cmd-name">
                                                         {text.clone()}
                                                     </span>
                                                 }.into_view(),
+                                                OutputPart::Styled { text, style } => view! {
+                                                    <span class="output" style=style.css()>
+                                                        {text.clone()}
+                                                    </span>
+                                                }.into_view(),
+                                                OutputPart::Highlight(text) => view! {
+                                                    <mark class="output highlight">
+                                                        {text.clone()}
+                                                    </mark>
+                                                }.into_view(),
+                                                OutputPart::Rule { fill, .. } => {
+                                                    let (fill, lead) = (fill.clone(), lead());
+                                                    view! {
+                                                        <span class="output">
+                                                            {move || screen::rule(&fill, columns.get().saturating_sub(2 * lead))}
+                                                        </span>
+                                                    }.into_view()
+                                                }
+                                                OutputPart::Wrap { text, hang } => {
+                                                    let (text, hang, lead) = (text.clone(), hang.clone(), lead());
+                                                    view! {
+                                                        <span class="output">
+                                                            {move || {
+                                                                let columns = columns.get();
+                                                                let rows = screen::wrap(&text, columns.saturating_sub(lead), columns.saturating_sub(table::width(&hang)));
+                                                                rows.join(&format!("\n{}", hang))
+                                                            }}
+                                                        </span>
+                                                    }.into_view()
+                                                }
                                                 OutputPart::Link { text, url } => view! {
                                                     <a href={url.clone()} target="_blank" class="terminal-link">
                                                         {text.clone()}
@@ -748,10 +1154,23 @@ fn Terminal() -> impl IntoView {
                                 }
                             }
                         />
+                        <div class="scroll-spacer" style:height=move || format!("{}px", visible.get().after)></div>
                     </div>
                     <Show when=move || show_input.get()>
                         <div class="terminal-input-line">
-                            <span class="prompt">"λ "</span>
+                            <Show when=move || last_status.get() != 0>
+                                <span class="prompt-status">{move || last_status.get()}</span>
+                            </Show>
+                            <span class="prompt" class:prompt-search=move || search.with(Option::is_some)>
+                                {move || match search.get() {
+                                    Some(state) => format!(
+                                        "({}reverse-i-search)`{}':",
+                                        if state.failed { "failed " } else { "" },
+                                        state.query,
+                                    ),
+                                    None => format!("{} λ ", vfs::display(&cwd.get())),
+                                }}
+                            </span>
                             <input
                                 type="text"
                                 id="cmd-input"
@@ -0,0 +1,220 @@
+// ANSI escape sequences -> OutputPart runs, so text written with normal
+// terminal conventions (SGR colors and attributes, OSC 8 hyperlinks) renders
+// styled. anything else that starts with ESC is dropped rather than shown.
+
+use super::OutputPart;
+
+const ESC: char = '\x1b';
+const BEL: char = '\x07';
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) enum Color {
+    // 0-15 are the theme's palette, 16-255 the xterm cube and gray ramp
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+// the 16 basic colors, tuned to sit on the terminal background
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0x1a, 0x1a, 0x24), (0xff, 0x55, 0x55), (0x00, 0xff, 0x88), (0xd4, 0xaf, 0x37),
+    (0x4f, 0x8c, 0xff), (0xc6, 0x78, 0xdd), (0x56, 0xd4, 0xdd), (0xe8, 0xe8, 0xe8),
+    (0x7a, 0x7a, 0x8a), (0xff, 0x7b, 0x7b), (0x66, 0xff, 0xb3), (0xf0, 0xd0, 0x60),
+    (0x7f, 0xaa, 0xff), (0xe0, 0x9e, 0xf0), (0x8c, 0xe8, 0xf0), (0xff, 0xff, 0xff),
+];
+
+impl Color {
+    fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Indexed(n @ 0..=15) => PALETTE[n as usize],
+            Color::Indexed(n @ 16..=231) => {
+                let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                let n = n - 16;
+                (level(n / 36), level(n / 6 % 6), level(n % 6))
+            }
+            Color::Indexed(n) => {
+                let gray = 8 + (n - 232) * 10;
+                (gray, gray, gray)
+            }
+            Color::Rgb(r, g, b) => (r, g, b),
+        }
+    }
+
+    fn css(self) -> String {
+        let (r, g, b) = self.rgb();
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+// the SGR state a run of text is drawn with
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub(super) struct Style {
+    pub(super) fg: Option<Color>,
+    pub(super) bg: Option<Color>,
+    pub(super) bold: bool,
+    pub(super) italic: bool,
+    pub(super) underline: bool,
+    pub(super) reverse: bool,
+}
+
+impl Style {
+    // inline css for the span - reverse swaps in the theme colors for
+    // whichever side was left at its default
+    pub(super) fn css(&self) -> String {
+        let fg = self.fg.map_or("var(--text-primary)".to_string(), Color::css);
+        let bg = self.bg.map(Color::css);
+        let (fg, bg) = match self.reverse {
+            true => (bg.unwrap_or("var(--bg-terminal)".to_string()), Some(fg)),
+            false => (fg, bg),
+        };
+        let mut css = format!("color: {};", fg);
+        if let Some(bg) = bg {
+            css.push_str(&format!(" background: {};", bg));
+        }
+        if self.bold {
+            css.push_str(" font-weight: 700;");
+        }
+        if self.italic {
+            css.push_str(" font-style: italic;");
+        }
+        if self.underline {
+            css.push_str(" text-decoration: underline;");
+        }
+        css
+    }
+
+    // apply one `ESC [ ... m` parameter list
+    fn apply(&mut self, params: &str) {
+        // `;` separates parameters, `:` sub-parameters (`38:2::r:g:b`)
+        let mut params = params.split(';').peekable();
+        while let Some(param) = params.next() {
+            if param.contains(':') {
+                let sub: Vec<&str> = param.split(':').collect();
+                let color = extended(&sub[1..]);
+                match sub[0] {
+                    "38" => self.fg = color.or(self.fg),
+                    "48" => self.bg = color.or(self.bg),
+                    _ => {}
+                }
+                continue;
+            }
+            // an empty parameter means 0, so a bare `ESC [ m` resets
+            let code = if param.is_empty() { 0 } else { param.parse::<u8>().unwrap_or(u8::MAX) };
+            match code {
+                0 => *self = Style::default(),
+                1 => self.bold = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.reverse = true,
+                22 => self.bold = false,
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.reverse = false,
+                30..=37 => self.fg = Some(Color::Indexed(code - 30)),
+                38 | 48 => {
+                    // `38;5;n` and `38;2;r;g;b` take the next params
+                    let count = match params.peek() {
+                        Some(&"5") => 2,
+                        Some(&"2") => 4,
+                        _ => 0,
+                    };
+                    let args: Vec<&str> = params.by_ref().take(count).collect();
+                    let color = extended(&args);
+                    if code == 38 {
+                        self.fg = color.or(self.fg);
+                    } else {
+                        self.bg = color.or(self.bg);
+                    }
+                }
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(Color::Indexed(code - 40)),
+                49 => self.bg = None,
+                90..=97 => self.fg = Some(Color::Indexed(code - 90 + 8)),
+                100..=107 => self.bg = Some(Color::Indexed(code - 100 + 8)),
+                _ => {}
+            }
+        }
+    }
+}
+
+// `5;n` or `2;r;g;b` (the colon form may have an empty color space id
+// before r: `2::r:g:b`)
+fn extended(args: &[&str]) -> Option<Color> {
+    let num = |s: &&str| s.parse::<u8>().ok();
+    match args {
+        ["5", n, ..] => num(n).map(Color::Indexed),
+        ["2", "", r, g, b, ..] | ["2", r, g, b, ..] => Some(Color::Rgb(num(r)?, num(g)?, num(b)?)),
+        _ => None,
+    }
+}
+
+// split `text` into parts - plain runs are `Text`, styled runs `Styled` and
+// OSC 8 hyperlinks `Link`. text without ESC comes back as a single `Text`
+pub(super) fn parse(text: &str) -> Vec<OutputPart> {
+    if !text.contains(ESC) {
+        return vec![OutputPart::Text(text.to_string())];
+    }
+    let mut parts = vec![];
+    let mut style = Style::default();
+    let mut link: Option<String> = None;
+    let mut run = String::new();
+
+    let flush = |run: &mut String, parts: &mut Vec<OutputPart>, style: Style, link: &Option<String>| {
+        if run.is_empty() {
+            return;
+        }
+        let text = std::mem::take(run);
+        parts.push(match (link, style == Style::default()) {
+            (Some(url), _) => OutputPart::Link { text, url: url.clone() },
+            (None, true) => OutputPart::Text(text),
+            (None, false) => OutputPart::Styled { text, style },
+        });
+    };
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != ESC {
+            run.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI - parameters, then a final byte in @..~
+            Some('[') => {
+                let mut params = String::new();
+                let mut last = None;
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        last = Some(c);
+                        break;
+                    }
+                    params.push(c);
+                }
+                if last == Some('m') {
+                    flush(&mut run, &mut parts, style, &link);
+                    style.apply(&params);
+                }
+            }
+            // OSC - up to BEL or ESC \
+            Some(']') => {
+                let mut body = String::new();
+                while let Some(c) = chars.next() {
+                    if c == BEL || (c == ESC && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                    body.push(c);
+                }
+                // `8;params;url` opens a hyperlink, an empty url closes it.
+                // content may come from a fetched file, so only web and
+                // mail links become clickable
+                if let Some(rest) = body.strip_prefix("8;") {
+                    flush(&mut run, &mut parts, style, &link);
+                    let url = rest.split_once(';').map_or("", |(_, url)| url);
+                    let safe = ["https://", "http://", "mailto:"].iter().any(|scheme| url.starts_with(scheme));
+                    link = safe.then(|| url.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    flush(&mut run, &mut parts, style, &link);
+    parts
+}
@@ -0,0 +1,1571 @@
+use leptos::*;
+
+use super::content::{self, portfolio, Source};
+use super::history::CommandHistory;
+use super::portfolio::{Domain, Project};
+use super::screen;
+use super::scrollback::{self, Scrollback};
+use super::tokenizer::{Args, Connector, Pipeline};
+use super::table::{Align, Border, Cell, Table};
+use super::tmpfs::{self, Cp, Mkdir, Mv, Rm, Touch};
+use super::vfs::{self, Cat, Cd, Find, Ls, Pwd, Tree};
+use super::{
+    get_about_output, get_contact_output, get_project_card, get_projects_compact, get_projects_output, get_skill_usage,
+    get_skills_output, find_matches, levenshtein,
+    OutputPart, TerminalLine,
+};
+
+// where a command shows up - core commands are listed in help and the boot menu,
+// utilities only in help, easter eggs get a hint line, hidden ones are left for
+// visitors to find
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) enum Category {
+    Core,
+    Utility,
+    EasterEgg,
+    Hidden,
+}
+
+// everything a handler can reach while it runs
+pub(super) struct Context<'a> {
+    pub(super) registry: &'a Registry,
+    // what is on screen, `set_history` to add to it
+    pub(super) scrollback: ReadSignal<Scrollback>,
+    pub(super) set_history: WriteSignal<Scrollback>,
+    pub(super) start_time: f64,
+    pub(super) history: StoredValue<CommandHistory>,
+    // (label, url) pairs from the last numbered `open` listing
+    pub(super) open_choices: StoredValue<Vec<(String, String)>>,
+    pub(super) cwd: RwSignal<String>,
+    // output of the previous pipeline stage, None when run on its own
+    pub(super) stdin: Option<Vec<TerminalLine>>,
+}
+
+// what a handler hands back - output lines plus an exit status
+pub(super) struct Output {
+    pub(super) lines: Vec<TerminalLine>,
+    pub(super) status: i32,
+    // wipe the screen before showing `lines` - also drops what earlier
+    // commands on the same line printed
+    pub(super) clear: bool,
+    // play the boot sequence once the line is done - nothing after it runs
+    pub(super) reboot: bool,
+}
+
+impl Output {
+    pub(super) fn new(status: i32, lines: Vec<TerminalLine>) -> Self {
+        Self { lines, status, clear: false, reboot: false }
+    }
+
+    // `lines` on an empty screen
+    pub(super) fn cleared(lines: Vec<TerminalLine>) -> Self {
+        Self { clear: true, ..Self::ok(lines) }
+    }
+
+    pub(super) fn ok(lines: Vec<TerminalLine>) -> Self {
+        Self::new(0, lines)
+    }
+
+    // a single indented message with a failing status
+    pub(super) fn error(status: i32, message: &str) -> Self {
+        Self::new(status, vec![TerminalLine::text("", &format!("  {}", message), false)])
+    }
+}
+
+impl From<Vec<TerminalLine>> for Output {
+    fn from(lines: Vec<TerminalLine>) -> Self {
+        Self::ok(lines)
+    }
+}
+
+// a terminal command - metadata plus a handler
+// argv[0] is the name the command was invoked as
+pub(super) trait Command {
+    fn name(&self) -> &'static str;
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+    fn summary(&self) -> &'static str;
+    fn usage(&self) -> &'static str;
+    // longer text for `man`, one paragraph per line
+    fn description(&self) -> &'static str {
+        self.summary()
+    }
+    // (flags, meaning) pairs for the OPTIONS section
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+    fn category(&self) -> Category {
+        Category::Core
+    }
+    // sample invocations, the first one doubles as the help hint
+    fn examples(&self) -> &'static [&'static str] {
+        &[]
+    }
+    // arguments are filesystem paths, Tab completes them from the cwd
+    fn takes_paths(&self) -> bool {
+        false
+    }
+    // candidates for the next argument, given the words typed so far
+    // (argv[0] included) - the completer filters them by prefix. `registry`
+    // is the one being completed against, for commands that take commands
+    fn complete(&self, _words: &[String], _registry: &Registry) -> Vec<String> {
+        vec![]
+    }
+    fn run(&self, argv: &[String], ctx: &Context) -> Output;
+}
+
+// single source of truth for commands - help, the boot menu and
+// "did you mean" suggestions are all generated from here
+pub(super) struct Registry {
+    commands: Vec<Box<dyn Command>>,
+}
+
+impl Registry {
+    pub(super) fn new() -> Self {
+        Self { commands: vec![] }
+    }
+
+    // every builtin, in the order help lists them
+    pub(super) fn builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(Projects);
+        registry.register(ProjectCard);
+        registry.register(Skills);
+        registry.register(About);
+        registry.register(Contact);
+        registry.register(Open);
+        registry.register(Clear);
+        registry.register(Help);
+        // pipeline utilities
+        registry.register(Grep);
+        registry.register(Head);
+        registry.register(Tail);
+        registry.register(Wc);
+        registry.register(Column);
+        registry.register(Tput);
+        registry.register(Echo);
+        registry.register(History);
+        registry.register(ScrollbackLimit);
+        registry.register(Man);
+        registry.register(Sync);
+        registry.register(Reboot);
+        // filesystem
+        registry.register(Pwd);
+        registry.register(Cd);
+        registry.register(Ls);
+        registry.register(Cat);
+        registry.register(Tree);
+        registry.register(Find);
+        registry.register(Touch);
+        registry.register(Mkdir);
+        registry.register(Cp);
+        registry.register(Mv);
+        registry.register(Rm);
+        // easter eggs
+        registry.register(Neofetch);
+        registry.register(Whoami);
+        registry.register(Sudo);
+        registry.register(Ping);
+        registry.register(Date);
+        registry.register(Uptime);
+        registry.register(Exit);
+        registry.register(Rust);
+        registry.register(True);
+        registry.register(False);
+        registry
+    }
+
+    pub(super) fn register(&mut self, command: impl Command + 'static) {
+        self.commands.push(Box::new(command));
+    }
+
+    pub(super) fn find(&self, name: &str) -> Option<&dyn Command> {
+        self.commands
+            .iter()
+            .find(|c| c.name() == name || c.aliases().contains(&name))
+            .map(|c| c.as_ref())
+    }
+
+    pub(super) fn in_category(&self, category: Category) -> impl Iterator<Item = &dyn Command> {
+        self.commands
+            .iter()
+            .filter(move |c| c.category() == category)
+            .map(|c| c.as_ref())
+    }
+
+    // names Tab completes to in command position - hidden ones stay hidden
+    pub(super) fn completion_names(&self) -> Vec<String> {
+        self.commands
+            .iter()
+            .filter(|c| c.category() != Category::Hidden)
+            .map(|c| c.name().to_string())
+            .collect()
+    }
+
+    // closest visible command name by edit distance
+    pub(super) fn suggest(&self, input: &str) -> Option<(&'static str, usize)> {
+        self.commands
+            .iter()
+            .filter(|c| c.category() != Category::Hidden)
+            .map(|c| (c.name(), levenshtein(input, c.name())))
+            .min_by_key(|(_, dist)| *dist)
+    }
+
+    // aligned `name  summary` rows for one category
+    pub(super) fn menu_lines(&self, category: Category) -> Vec<TerminalLine> {
+        let mut table = Table::new().gap(3);
+        for command in self.in_category(category) {
+            table.push(vec![Cell::cmd(command.name()), command.summary().into()]);
+        }
+        table.render()
+    }
+
+    pub(super) fn execute(&self, argv: &[String], ctx: &Context) -> Output {
+        let Some(name) = argv.first() else {
+            return Output::ok(vec![]);
+        };
+        if let Some(command) = self.find(&name.to_lowercase()) {
+            return command.run(argv, ctx);
+        }
+
+        let input = argv.join(" ");
+        let mut lines = vec![TerminalLine::text("", &format!("  command not found: {}", input), false)];
+        if let Some((closest, dist)) = self.suggest(name).filter(|(_, dist)| *dist <= 2) {
+            lines.push(TerminalLine::text("", &format!("  did you mean '{}'? (dist: {})", closest, dist), false));
+        }
+        lines.push(TerminalLine::text("", "  type 'help' for commands", false));
+        Output::new(127, lines)
+    }
+
+    // run `a | b | c`, each stage reading the previous stage's lines
+    // the pipeline's status is the last stage's
+    pub(super) fn execute_pipeline(&self, stages: &[Vec<String>], ctx: &Context) -> Output {
+        let mut output = Output::ok(vec![]);
+        for (i, argv) in stages.iter().enumerate() {
+            let stdin = if i == 0 { None } else { Some(std::mem::take(&mut output.lines)) };
+            let stage = Context { stdin, ..*ctx };
+            output = self.execute(argv, &stage);
+            if output.reboot {
+                break;
+            }
+        }
+        output
+    }
+
+    // run a whole input line - `;`, `&&` and `||` lists of pipelines
+    // words are expanded just before their pipeline runs so `$?` is current
+    pub(super) fn execute_line(&self, pipelines: &[Pipeline], last_status: i32, ctx: &Context) -> Output {
+        let mut status = last_status;
+        let mut lines = vec![];
+        let mut clear = false;
+        for pipeline in pipelines {
+            let run = match pipeline.connector {
+                Connector::Always => true,
+                Connector::And => status == 0,
+                Connector::Or => status != 0,
+            };
+            if !run {
+                continue;
+            }
+            let lookup = |name: &str| match name {
+                "?" => Some(status.to_string()),
+                "COLUMNS" => Some(screen::columns().to_string()),
+                _ => None,
+            };
+            let stages: Vec<Vec<String>> = pipeline
+                .stages
+                .iter()
+                .map(|argv| argv.iter().map(|word| word.expand(lookup)).collect())
+                .collect();
+            let mut output = self.execute_pipeline(&stages, ctx);
+            // the screen is about to be wiped - drop what the line printed so
+            // far and skip the rest of it
+            if output.reboot {
+                return output;
+            }
+            if let Some(redirect) = &pipeline.redirect {
+                output = redirect_output(output, &redirect.target.expand(lookup), redirect.append, ctx);
+            }
+            status = output.status;
+            if output.clear {
+                lines.clear();
+                clear = true;
+            }
+            lines.extend(output.lines);
+        }
+        Output { clear, ..Output::new(status, lines) }
+    }
+}
+
+// `> file` / `>> file` - a successful pipeline's output goes to the file
+// instead of the screen. a failing one is shown as is, as if on stderr
+fn redirect_output(output: Output, target: &str, append: bool, ctx: &Context) -> Output {
+    if output.status != 0 {
+        return output;
+    }
+    let text = vfs::to_text(&output.lines);
+    match tmpfs::write(&vfs::resolve(&ctx.cwd.get_untracked(), target), &text, append) {
+        Ok(()) => Output::ok(vec![]),
+        Err(err) => Output::error(1, &format!("vanguard: {}: {}", target, err)),
+    }
+}
+
+struct Projects;
+
+impl Command for Projects {
+    fn name(&self) -> &'static str { "projects" }
+    fn aliases(&self) -> &'static [&'static str] { &["repos"] }
+    fn summary(&self) -> &'static str { "View portfolio projects" }
+    fn usage(&self) -> &'static str { "projects [-l] [--category C] [--tag T] [--has-demo] [--sort name]" }
+    fn description(&self) -> &'static str {
+        "Lists portfolio projects grouped by domain: industrial edge, systems architecture, full stack and WASM tooling. Each entry has a summary, headline metrics and demo/source links.\nFilters combine, so `projects --category edge --has-demo` lists live industrial edge demos. Exits 1 when nothing matches."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-l, --long", "One line per project (▶ marks a live demo)"),
+            ("--category C", "Only one domain: edge, systems, full-stack, wasm"),
+            ("--tag T", "Only projects tagged T, e.g. rust"),
+            ("--has-demo", "Only projects with a live demo"),
+            ("--sort name", "Alphabetical"),
+        ]
+    }
+    fn examples(&self) -> &'static [&'static str] {
+        &["projects --category edge", "projects --tag rust -l", "projects --has-demo --sort name"]
+    }
+
+    fn complete(&self, words: &[String], _registry: &Registry) -> Vec<String> {
+        let portfolio = portfolio();
+        match words.last().map(String::as_str) {
+            Some("--category") => Domain::ALL.iter().map(|d| d.slug().to_string()).collect(),
+            Some("--tag") => portfolio.projects.iter().flat_map(|p| p.tags.iter().map(|t| t.to_lowercase())).collect(),
+            // `date` only once the content has dates to sort by
+            Some("--sort") if portfolio.projects.iter().any(|p| p.date.is_some()) => vec!["name".into(), "date".into()],
+            Some("--sort") => vec!["name".into()],
+            _ => ["-l", "--category", "--tag", "--has-demo", "--sort"].map(String::from).to_vec(),
+        }
+    }
+
+    fn run(&self, argv: &[String], _ctx: &Context) -> Output {
+        let args = Args::parse(argv, &["category", "tag", "sort"]);
+        let usage_error = |msg: &str| Output::new(2, vec![
+            TerminalLine::text("", &format!("  projects: {}", msg), false),
+            TerminalLine::text("", &format!("  usage: {}", self.usage()), false),
+        ]);
+
+        let (category, tag, sort) = match (args.value(&["category"]), args.value(&["tag"]), args.value(&["sort"])) {
+            (Ok(category), Ok(tag), Ok(sort)) => (category, tag, sort),
+            (Err(err), ..) | (_, Err(err), _) | (.., Err(err)) => return usage_error(&err.to_string()),
+        };
+        // `--category=` and `--tag ""` would otherwise match everything or nothing
+        for (name, value) in [("category", category), ("tag", tag), ("sort", sort)] {
+            if value == Some("") {
+                return usage_error(&format!("option '--{}' requires a value", name));
+            }
+        }
+        let domain = match category {
+            None => None,
+            Some(query) => match Domain::ALL.into_iter().find(|d| d.matches(query)) {
+                Some(domain) => Some(domain),
+                None => {
+                    let known: Vec<&str> = Domain::ALL.iter().map(|d| d.slug()).collect();
+                    return usage_error(&format!("unknown category '{}' (one of: {})", query, known.join(", ")));
+                }
+            },
+        };
+        let tag = tag.map(str::to_lowercase);
+        let has_demo = args.has(&["has-demo"]);
+
+        let portfolio = portfolio();
+        let mut projects: Vec<&Project> = portfolio
+            .projects
+            .iter()
+            .filter(|p| domain.is_none_or(|d| p.category == d))
+            .filter(|p| tag.as_ref().is_none_or(|t| p.tags.iter().any(|pt| pt.to_lowercase() == *t)))
+            .filter(|p| !has_demo || p.demo_url.is_some())
+            .collect();
+
+        let dated = portfolio.projects.iter().any(|p| p.date.is_some());
+        match sort {
+            None => {}
+            Some("name") => projects.sort_by_key(|p| p.title.to_lowercase()),
+            // newest first, undated projects keep file order at the end
+            Some("date") if !dated => return usage_error("cannot sort by 'date' - no project has a date yet"),
+            Some("date") => projects.sort_by(|a, b| b.date.is_some().cmp(&a.date.is_some()).then_with(|| b.date.cmp(&a.date))),
+            Some(other) => return usage_error(&format!("cannot sort by '{}' (name)", other)),
+        }
+
+        if projects.is_empty() {
+            return Output::error(1, "no projects match");
+        }
+        if args.has(&["l", "long"]) {
+            get_projects_compact(&projects).into()
+        } else {
+            get_projects_output(&projects).into()
+        }
+    }
+}
+
+struct ProjectCard;
+
+impl Command for ProjectCard {
+    fn name(&self) -> &'static str { "project" }
+    fn summary(&self) -> &'static str { "Full details for one project" }
+    fn usage(&self) -> &'static str { "project <name>" }
+    fn description(&self) -> &'static str {
+        "Prints a card for one project: overview, architecture notes, metrics, tech tags, links and related projects.\nNAME can be part of the title, the slug shown under RELATED, or the repository name. Misspellings resolve to the closest project."
+    }
+    fn examples(&self) -> &'static [&'static str] { &["project guardian", "project raft"] }
+
+    fn complete(&self, words: &[String], _registry: &Registry) -> Vec<String> {
+        if words.len() == 1 { portfolio().projects.iter().map(Project::slug).collect() } else { vec![] }
+    }
+
+    fn run(&self, argv: &[String], _ctx: &Context) -> Output {
+        if argv.len() < 2 {
+            return Output::error(2, &format!("usage: {}", self.usage()));
+        }
+        let query = argv[1..].join(" ");
+        let portfolio = portfolio();
+        let ranked = rank_projects(&portfolio.projects, &query);
+        let Some(&(best, score)) = ranked.first() else {
+            return Output::error(1, &format!("project: nothing matches '{}' (see 'projects -l')", query));
+        };
+
+        let mut lines = vec![];
+        if score >= FUZZY {
+            // a typo - say what was picked and what else came close
+            let close: Vec<&str> = ranked[1..].iter().filter(|(_, s)| *s == score).map(|(p, _)| p.title.as_str()).collect();
+            let also = if close.is_empty() { String::new() } else { format!(" (also close: {})", close.join(", ")) };
+            lines.push(TerminalLine::text("", "", false));
+            lines.push(TerminalLine::text("", &format!("  no exact match for '{}' - showing {}{}", query, best.title, also), false));
+        }
+        lines.extend(get_project_card(best, &portfolio.projects));
+        lines.into()
+    }
+}
+
+// scores at or above this came from edit distance rather than a substring
+const FUZZY: usize = 5;
+
+// projects matching `query`, best first - exact names, then prefixes of the
+// slug/title/repo, then whole title words, then substrings, then anything
+// within a few edits (ranked by `levenshtein`)
+fn rank_projects<'a>(projects: &'a [Project], query: &str) -> Vec<(&'a Project, usize)> {
+    let query = query.trim().to_lowercase();
+    let slug_query = query.split_whitespace().collect::<Vec<_>>().join("-");
+    let max_edits = (query.chars().count() / 3).max(2);
+
+    let mut ranked: Vec<(&Project, usize)> = projects
+        .iter()
+        .filter_map(|project| {
+            let names = [project.slug(), project.title.to_lowercase(), project.repo().to_lowercase()];
+            let words: Vec<String> = names[0].split('-').map(String::from).collect();
+            let score = if names.iter().any(|n| *n == query || *n == slug_query) {
+                0
+            } else if names.iter().any(|n| n.starts_with(&query) || n.starts_with(&slug_query)) {
+                1
+            } else if words.contains(&query) {
+                2
+            } else if words.iter().any(|w| w.starts_with(&query)) {
+                3
+            } else if names.iter().any(|n| n.contains(&query) || n.contains(&slug_query)) {
+                4
+            } else {
+                let edits = names.iter().chain(&words).map(|n| levenshtein(&query, n)).min()?;
+                if edits > max_edits {
+                    return None;
+                }
+                FUZZY + edits
+            };
+            Some((project, score))
+        })
+        .collect();
+    // stable, so equal scores keep portfolio order
+    ranked.sort_by_key(|(_, score)| *score);
+    ranked
+}
+
+struct Skills;
+
+impl Command for Skills {
+    fn name(&self) -> &'static str { "skills" }
+    fn aliases(&self) -> &'static [&'static str] { &["stack", "tech"] }
+    fn summary(&self) -> &'static str { "Technical stack and expertise" }
+    fn usage(&self) -> &'static str { "skills [-g] [--sort] [name]" }
+    fn description(&self) -> &'static str {
+        "Shows the technical stack grouped by specialty area.\nSkills can carry a self-assessed level from 1 to 10 and years of use; --graph draws them as bars, unrated skills as a dotted track.\nWith a NAME, shows that skill and every project that used it. The tech badges on a `project` card run this when clicked."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-g, --graph", "Bar chart of level and years per skill"),
+            ("-s, --sort", "Strongest skills first in each area, unrated ones last"),
+        ]
+    }
+    fn examples(&self) -> &'static [&'static str] { &["skills --graph", "skills -g --sort", "skills rust"] }
+
+    fn complete(&self, _words: &[String], _registry: &Registry) -> Vec<String> {
+        let mut options = vec!["--graph".to_string(), "--sort".to_string()];
+        options.extend(portfolio().skills.iter().flat_map(|a| &a.items).map(|s| s.name.clone()));
+        options
+    }
+
+    fn run(&self, argv: &[String], _ctx: &Context) -> Output {
+        let args = Args::parse(argv, &[]);
+        if args.positional.is_empty() {
+            return get_skills_output(args.has(&["g", "graph"]), args.has(&["s", "sort"])).into();
+        }
+
+        // `skills wasi 0.2` works without quotes
+        let name = args.positional.join(" ");
+        let portfolio = portfolio();
+        if let Some((area, skill)) = portfolio.skill(&name) {
+            return get_skill_usage(area, skill, &portfolio.projects).into();
+        }
+        let closest = portfolio
+            .skills
+            .iter()
+            .flat_map(|a| &a.items)
+            .map(|s| (s.name.as_str(), levenshtein(&name.to_lowercase(), &s.name.to_lowercase())))
+            .min_by_key(|(_, dist)| *dist)
+            .filter(|(_, dist)| *dist <= 2);
+        match closest {
+            Some((closest, _)) => Output::error(1, &format!("skills: unknown skill '{}' - did you mean '{}'?", name, closest)),
+            None => Output::error(1, &format!("skills: unknown skill '{}'", name)),
+        }
+    }
+}
+
+struct About;
+
+impl Command for About {
+    fn name(&self) -> &'static str { "about" }
+    fn summary(&self) -> &'static str { "Profile and bio" }
+    fn usage(&self) -> &'static str { "about" }
+    fn description(&self) -> &'static str {
+        "Prints the profile: engineering focus, flagship work and core expertise."
+    }
+
+    fn run(&self, _argv: &[String], _ctx: &Context) -> Output {
+        get_about_output().into()
+    }
+}
+
+struct Contact;
+
+impl Command for Contact {
+    fn name(&self) -> &'static str { "contact" }
+    fn aliases(&self) -> &'static [&'static str] { &["email"] }
+    fn summary(&self) -> &'static str { "Get in touch" }
+    fn usage(&self) -> &'static str { "contact" }
+    fn description(&self) -> &'static str {
+        "Prints email and GitHub contact links."
+    }
+
+    fn run(&self, _argv: &[String], _ctx: &Context) -> Output {
+        get_contact_output().into()
+    }
+}
+
+struct Open;
+
+impl Command for Open {
+    fn name(&self) -> &'static str { "open" }
+    fn summary(&self) -> &'static str { "Open a project, GitHub or email link" }
+    fn usage(&self) -> &'static str { "open <project> [demo|github] | open github | open email | open <n>" }
+    fn description(&self) -> &'static str {
+        "Opens a link from the keyboard. `open <project>` opens its live demo or repository, `open github` the GitHub profile and `open email` a new mail.\nWhen more than one link matches they are listed with numbers; `open <n>` then opens that one."
+    }
+    fn examples(&self) -> &'static [&'static str] { &["open guardian demo", "open github", "open email"] }
+
+    fn complete(&self, words: &[String], _registry: &Registry) -> Vec<String> {
+        match words.len() {
+            1 => {
+                let mut names: Vec<String> = portfolio().projects.iter().map(Project::slug).collect();
+                names.extend(["github".to_string(), "email".to_string()]);
+                names
+            }
+            2 if !matches!(words[1].as_str(), "github" | "email") => vec!["demo".into(), "github".into()],
+            _ => vec![],
+        }
+    }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let words: Vec<String> = argv[1..].iter().map(|w| w.to_lowercase()).collect();
+        let portfolio = portfolio();
+        let links: Vec<(String, String)> = match words.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+            [] => return Output::error(2, &format!("usage: {}", self.usage())),
+            [n] if n.parse::<usize>().is_ok() => {
+                let n: usize = n.parse().unwrap_or_default();
+                match ctx.open_choices.with_value(|choices| choices.get(n.wrapping_sub(1)).cloned()) {
+                    Some(link) => vec![link],
+                    None => return Output::error(1, &format!("open: no link {} - run 'open <project>' for a list", n)),
+                }
+            }
+            ["email" | "mail"] => vec![("Email".into(), format!("mailto:{}", portfolio.contact.email))],
+            ["github"] => vec![("GitHub profile".into(), portfolio.contact.github_url())],
+            [query @ .., kind] if matches!(*kind, "demo" | "github" | "gh" | "source") => {
+                match project_links_for(&portfolio.projects, &query.join(" "), Some(kind)) {
+                    Ok(links) => links,
+                    Err(err) => return Output::error(1, &err),
+                }
+            }
+            query => match project_links_for(&portfolio.projects, &query.join(" "), None) {
+                Ok(links) => links,
+                Err(err) => return Output::error(1, &err),
+            },
+        };
+
+        if let [(label, url)] = links.as_slice() {
+            open_url(url);
+            return vec![TerminalLine::with_link("", &format!("  opening {} → ", label), url, url, "")].into();
+        }
+        let mut lines = vec![TerminalLine::text("", "", false)];
+        lines.extend(links.iter().enumerate().map(|(i, (label, url))| {
+            TerminalLine::with_link("", &format!("  {}) {} ", i + 1, label), url, url, "")
+        }));
+        lines.push(TerminalLine::text("", "", false));
+        lines.push(TerminalLine::text("", "  type 'open <n>' to choose", false));
+        ctx.open_choices.set_value(links);
+        lines.into()
+    }
+}
+
+// links of the best-matching project(s) - `kind` narrows to the demo or repo
+fn project_links_for(projects: &[Project], query: &str, kind: Option<&str>) -> Result<Vec<(String, String)>, String> {
+    let ranked = rank_projects(projects, query);
+    let Some(&(_, best)) = ranked.first() else {
+        return Err(format!("open: nothing matches '{}' (see 'projects -l')", query));
+    };
+    let mut links = vec![];
+    for (project, _) in ranked.iter().filter(|(_, score)| *score == best) {
+        if let (Some(url), None | Some("demo")) = (&project.demo_url, kind) {
+            links.push((format!("{} - Live Demo", project.title), url.clone()));
+        }
+        if kind != Some("demo") {
+            links.push((format!("{} - GitHub", project.title), project.github_url.clone()));
+        }
+    }
+    if links.is_empty() {
+        return Err(format!("open: {} has no live demo", ranked[0].0.title));
+    }
+    Ok(links)
+}
+
+// a new tab for web links, the mail client for mailto:
+fn open_url(url: &str) {
+    if url.starts_with("mailto:") {
+        let _ = window().location().set_href(url);
+    } else {
+        let _ = window().open_with_url_and_target(url, "_blank");
+    }
+}
+
+struct Clear;
+
+impl Command for Clear {
+    fn name(&self) -> &'static str { "clear" }
+    fn aliases(&self) -> &'static [&'static str] { &["cls"] }
+    fn summary(&self) -> &'static str { "Reset terminal" }
+    fn usage(&self) -> &'static str { "clear" }
+    fn description(&self) -> &'static str {
+        "Clears the terminal output. Ctrl+L does the same without losing the line being typed."
+    }
+
+    fn run(&self, _argv: &[String], _ctx: &Context) -> Output {
+        Output::cleared(vec![TerminalLine::text("[ok]", " cleared.", true)])
+    }
+}
+
+struct Help;
+
+impl Command for Help {
+    fn name(&self) -> &'static str { "help" }
+    fn aliases(&self) -> &'static [&'static str] { &["h", "?"] }
+    fn summary(&self) -> &'static str { "List all commands" }
+    fn usage(&self) -> &'static str { "help" }
+    fn description(&self) -> &'static str {
+        "Lists the available commands and utilities. The list is generated from the command registry, so it always matches what the terminal understands."
+    }
+
+    fn run(&self, _argv: &[String], ctx: &Context) -> Output {
+        let eggs: Vec<&str> = ctx
+            .registry
+            .in_category(Category::EasterEgg)
+            .map(|c| c.examples().first().copied().unwrap_or(c.name()))
+            .collect();
+
+        let mut lines = vec![
+            TerminalLine::text("", "", false),
+            TerminalLine::section("", "  > AVAILABLE COMMANDS"),
+            TerminalLine::text("", "", false),
+        ];
+        lines.extend(ctx.registry.menu_lines(Category::Core));
+        lines.extend([
+            TerminalLine::text("", "", false),
+            TerminalLine::section("", "  > UTILITIES"),
+            TerminalLine::text("", "", false),
+        ]);
+        lines.extend(ctx.registry.menu_lines(Category::Utility));
+        lines.extend([
+            TerminalLine::text("", "", false),
+            TerminalLine::section("", "  > EASTER EGGS"),
+            TerminalLine::text("", &format!("  Try: {}", eggs.join(", ")), false),
+            TerminalLine::text("", "", false),
+            TerminalLine::text("", "  Type 'man <command>' for details.", false),
+            TerminalLine::text("", "", false),
+        ]);
+        lines.into()
+    }
+}
+
+// pipeline utilities - these read the previous stage from ctx.stdin
+
+// parse a line count from `-n N` or the old-style `-N`
+fn line_count(args: &Args, default: usize) -> Result<usize, String> {
+    let raw = args
+        .value(&["n", "lines"])
+        .map_err(|err| err.to_string())?
+        .or_else(|| args.positional.iter().find_map(|a| a.strip_prefix('-')));
+    match raw {
+        None => Ok(default),
+        Some(raw) => raw.parse().map_err(|_| format!("invalid number of lines: '{}'", raw)),
+    }
+}
+
+struct Grep;
+
+impl Command for Grep {
+    fn name(&self) -> &'static str { "grep" }
+    fn summary(&self) -> &'static str { "Search piped output or files" }
+    fn usage(&self) -> &'static str { "grep [-i] [-v] [-c] [-n] [-r] <pattern> [path...]" }
+    fn description(&self) -> &'static str {
+        "Keeps the lines of piped output that contain PATTERN, or searches the files at PATH (-r for whole directories). With neither, searches everything under the current directory. Matches are highlighted.\nMatching is smart-case: an all-lowercase pattern ignores case, a pattern with capitals is matched exactly. Exits 1 when nothing matches, 2 on a bad path."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-i, --ignore-case", "Ignore case even if PATTERN has capitals"),
+            ("-v, --invert-match", "Keep the lines that do not match"),
+            ("-c, --count", "Print the number of matching lines instead"),
+            ("-n, --line-number", "Prefix each line with its line number"),
+            ("-r, --recursive", "Search directories and everything below them"),
+        ]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["grep rust", "grep -rn kafka projects", "projects | grep wasm", "skills | grep -v rust"] }
+    fn takes_paths(&self) -> bool { true }
+
+    fn complete(&self, _words: &[String], _registry: &Registry) -> Vec<String> {
+        vec!["-i".into(), "-v".into(), "-c".into(), "-n".into(), "-r".into()]
+    }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &[]);
+        let Some((pattern, paths)) = args.positional.split_first() else {
+            return Output::error(2, &format!("usage: {}", self.usage()));
+        };
+
+        // smart case: an all-lowercase pattern matches any case
+        let ignore_case = args.has(&["i", "ignore-case"]) || !pattern.chars().any(char::is_uppercase);
+        let invert = args.has(&["v", "invert-match"]);
+        let number = args.has(&["n", "line-number"]);
+        let recursive = args.has(&["r", "R", "recursive"]);
+        // `grep ""` matches every line, like grep does
+        let keep = |line: &TerminalLine| (pattern.is_empty() || !find_matches(&line.plain_text(), pattern, ignore_case).is_empty()) != invert;
+        let show = |line: &TerminalLine| if invert { line.clone() } else { line.highlighted(pattern, ignore_case) };
+
+        // bad paths and unreadable files, reported ahead of the matches
+        let mut errors = vec![];
+        let mut matches = vec![];
+        match &ctx.stdin {
+            Some(input) if paths.is_empty() => {
+                for (i, line) in input.iter().enumerate() {
+                    if keep(line) {
+                        matches.push(grep_line(show(line), None, number.then_some(i + 1)));
+                    }
+                }
+            }
+            _ => {
+                // a bare `grep rust` searches the current directory, like `grep -r rust`
+                let (paths, recursive) = match paths {
+                    [] => (vec![String::new()], true),
+                    paths => (paths.to_vec(), recursive),
+                };
+                let show_names = recursive || paths.len() > 1;
+                let portfolio = portfolio();
+                let root = vfs::tree(&portfolio);
+                let cwd = ctx.cwd.get_untracked();
+                for path in &paths {
+                    let abs = vfs::resolve(&cwd, if path.is_empty() { "." } else { path });
+                    let Some((_, node)) = vfs::lookup(&root, &abs) else {
+                        errors.push(TerminalLine::text("", &format!("  grep: {}: No such file or directory", path), false));
+                        continue;
+                    };
+                    if node.is_dir() && !recursive {
+                        errors.push(TerminalLine::text("", &format!("  grep: {}: Is a directory", path), false));
+                        continue;
+                    }
+                    // dotfiles are only searched when named
+                    let mut found = vec![];
+                    vfs::descend(node, path, false, &mut found);
+                    for (file_path, node) in found {
+                        let vfs::Node::File(file) = node else {
+                            continue;
+                        };
+                        let text = match vfs::text(file, &portfolio) {
+                            Ok(text) => text,
+                            Err(err) => {
+                                errors.push(TerminalLine::text("", &format!("  grep: {}: {}", file_path, err), false));
+                                continue;
+                            }
+                        };
+                        for (i, line) in text.lines().enumerate() {
+                            let line = TerminalLine::text("", &format!("  {}", line), false);
+                            if keep(&line) {
+                                matches.push(grep_line(show(&line), show_names.then_some(file_path.as_str()), number.then_some(i + 1)));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // like grep, "nothing matched" is a failure and a bad path is worse
+        let status = if !errors.is_empty() { 2 } else if matches.is_empty() { 1 } else { 0 };
+        if args.has(&["c", "count"]) {
+            matches = vec![TerminalLine::text("", &format!("  {}", matches.len()), false)];
+        }
+        errors.extend(matches);
+        Output::new(status, errors)
+    }
+}
+
+// a grep result line - `name:` and `n:` go between the indent and the text
+fn grep_line(mut line: TerminalLine, name: Option<&str>, number: Option<usize>) -> TerminalLine {
+    if name.is_none() && number.is_none() {
+        return line;
+    }
+    let mut parts = vec![OutputPart::Text("  ".to_string())];
+    if let Some(name) = name {
+        parts.push(OutputPart::CmdName(name.to_string()));
+        parts.push(OutputPart::Text(":".to_string()));
+    }
+    if let Some(number) = number {
+        parts.push(OutputPart::Text(format!("{}:", number)));
+    }
+    if let Some(OutputPart::Text(first)) = line.parts.first_mut() {
+        *first = first.strip_prefix("  ").unwrap_or(first).to_string();
+    }
+    parts.append(&mut line.parts);
+    line.parts = parts;
+    line
+}
+
+struct Head;
+
+impl Command for Head {
+    fn name(&self) -> &'static str { "head" }
+    fn summary(&self) -> &'static str { "First lines of piped output" }
+    fn usage(&self) -> &'static str { "head [-n N]" }
+    fn description(&self) -> &'static str {
+        "Keeps the first N lines of piped output (10 by default)."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-n, --lines N", "Number of lines to keep"),
+            ("-N", "Same as -n N"),
+        ]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["skills | head -5"] }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &["n", "lines"]);
+        let count = match line_count(&args, 10) {
+            Ok(count) => count,
+            Err(err) => return Output::error(2, &format!("head: {}", err)),
+        };
+        let input = ctx.stdin.clone().unwrap_or_default();
+        input.into_iter().take(count).collect::<Vec<_>>().into()
+    }
+}
+
+struct Tail;
+
+impl Command for Tail {
+    fn name(&self) -> &'static str { "tail" }
+    fn summary(&self) -> &'static str { "Last lines of piped output" }
+    fn usage(&self) -> &'static str { "tail [-n N]" }
+    fn description(&self) -> &'static str {
+        "Keeps the last N lines of piped output (10 by default)."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-n, --lines N", "Number of lines to keep"),
+            ("-N", "Same as -n N"),
+        ]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["projects | tail -n 4"] }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &["n", "lines"]);
+        let count = match line_count(&args, 10) {
+            Ok(count) => count,
+            Err(err) => return Output::error(2, &format!("tail: {}", err)),
+        };
+        let input = ctx.stdin.clone().unwrap_or_default();
+        let skip = input.len().saturating_sub(count);
+        input.into_iter().skip(skip).collect::<Vec<_>>().into()
+    }
+}
+
+struct Wc;
+
+impl Command for Wc {
+    fn name(&self) -> &'static str { "wc" }
+    fn summary(&self) -> &'static str { "Count lines, words and chars" }
+    fn usage(&self) -> &'static str { "wc [-l] [-w] [-c]" }
+    fn description(&self) -> &'static str {
+        "Counts lines, words and characters of piped output. With no options all three are printed."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-l, --lines", "Count lines"),
+            ("-w, --words", "Count words"),
+            ("-c, --chars", "Count characters"),
+        ]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["projects | grep github | wc -l"] }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &[]);
+        let input = ctx.stdin.as_deref().unwrap_or_default();
+        let text: Vec<String> = input.iter().map(TerminalLine::plain_text).collect();
+        let lines = text.len();
+        let words: usize = text.iter().map(|t| t.split_whitespace().count()).sum();
+        let chars: usize = text.iter().map(|t| t.chars().count()).sum();
+
+        let mut counts = vec![];
+        if args.has(&["l", "lines"]) { counts.push(lines); }
+        if args.has(&["w", "words"]) { counts.push(words); }
+        if args.has(&["c", "chars"]) { counts.push(chars); }
+        if counts.is_empty() {
+            counts = vec![lines, words, chars];
+        }
+        let counts: Vec<String> = counts.iter().map(|n| n.to_string()).collect();
+        vec![TerminalLine::text("", &format!("  {}", counts.join("  ")), false)].into()
+    }
+}
+
+struct Column;
+
+impl Command for Column {
+    fn name(&self) -> &'static str { "column" }
+    fn summary(&self) -> &'static str { "Line piped output up in columns" }
+    fn usage(&self) -> &'static str { "column [-s SEP] [-H] [-b STYLE]" }
+    fn description(&self) -> &'static str {
+        "Splits each line of piped output into fields and lines them up as a table. Columns holding only numbers are right-aligned."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-s, --separator SEP", "Split fields on SEP instead of whitespace"),
+            ("-H, --headers", "Treat the first line as the header row"),
+            ("-b, --border STYLE", "Draw a border: single, rounded, double or heavy"),
+        ]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] {
+        &["history | column", "echo -e 'name,stars\\nvanguard,42' | column -s , -H -b rounded"]
+    }
+
+    fn complete(&self, words: &[String], _registry: &Registry) -> Vec<String> {
+        match words.last().map(String::as_str) {
+            Some("-b" | "--border") => Border::NAMED.iter().map(|(name, _)| name.to_string()).collect(),
+            _ => ["-s", "-H", "-b"].map(String::from).to_vec(),
+        }
+    }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &["s", "separator", "b", "border"]);
+        let (border, separator) = match (args.value(&["b", "border"]), args.value(&["s", "separator"])) {
+            (Ok(border), Ok(separator)) => (border, separator),
+            (Err(err), _) | (_, Err(err)) => return Output::error(2, &format!("column: {}", err)),
+        };
+        let border = match border {
+            None => Border::None,
+            Some(name) => match Border::from_name(name) {
+                Some(border) => border,
+                None => return Output::error(2, &format!("column: unknown border '{}' (single, rounded, double, heavy)", name)),
+            },
+        };
+        let separator = separator.filter(|sep| !sep.is_empty());
+
+        let input = ctx.stdin.as_deref().unwrap_or_default();
+        let mut rows: Vec<Vec<String>> = input
+            .iter()
+            .map(TerminalLine::plain_text)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| match separator {
+                Some(sep) => line.trim().split(sep).map(|field| field.trim().to_string()).collect(),
+                None => line.split_whitespace().map(String::from).collect(),
+            })
+            .collect();
+        let headers = if args.has(&["H", "headers"]) && !rows.is_empty() { Some(rows.remove(0)) } else { None };
+
+        let mut table = Table::new().border(border);
+        if let Some(headers) = &headers {
+            table = table.headers(&headers.iter().map(String::as_str).collect::<Vec<_>>());
+        }
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for column in 0..columns {
+            let mut fields = rows.iter().filter_map(|row| row.get(column)).peekable();
+            if fields.peek().is_some() && fields.all(|field| field.parse::<f64>().is_ok()) {
+                table = table.align(column, Align::Right);
+            }
+        }
+        for row in rows {
+            table.push(row.into_iter().map(Cell::from).collect());
+        }
+        table.render().into()
+    }
+}
+
+struct Tput;
+
+impl Command for Tput {
+    fn name(&self) -> &'static str { "tput" }
+    fn summary(&self) -> &'static str { "Query terminal capabilities" }
+    fn usage(&self) -> &'static str { "tput cols|colors" }
+    fn description(&self) -> &'static str {
+        "Prints a terminal capability. `cols` is the width of the terminal in characters, measured from the window and font size and updated on resize - the same value `$COLUMNS` expands to. `colors` is the size of the ANSI palette.\nExits 4 for an unknown capability."
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["tput cols", "echo $COLUMNS"] }
+
+    fn complete(&self, words: &[String], _registry: &Registry) -> Vec<String> {
+        if words.len() == 1 { vec!["cols".into(), "colors".into()] } else { vec![] }
+    }
+
+    fn run(&self, argv: &[String], _ctx: &Context) -> Output {
+        let value = match argv.get(1).map(String::as_str) {
+            Some("cols") => screen::columns(),
+            Some("colors") => 256,
+            Some(other) => return Output::error(4, &format!("tput: unknown terminfo capability '{}'", other)),
+            None => return Output::error(2, &format!("usage: {}", self.usage())),
+        };
+        vec![TerminalLine::text("", &format!("  {}", value), false)].into()
+    }
+}
+
+struct Echo;
+
+impl Command for Echo {
+    fn name(&self) -> &'static str { "echo" }
+    fn summary(&self) -> &'static str { "Print arguments" }
+    fn usage(&self) -> &'static str { "echo [-e] [text...]" }
+    fn description(&self) -> &'static str {
+        "Prints its arguments separated by spaces. Variables such as $? are expanded first. ANSI color escapes are rendered, so `echo -e \"\\e[1;32mok\\e[0m\"` prints a bold green ok."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-e", "Interpret \\e, \\033, \\x1b, \\n, \\t and \\\\")]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["echo $?", "echo \"hello world\"", "echo -e \"\\e[33mgold\\e[0m\""] }
+
+    fn run(&self, argv: &[String], _ctx: &Context) -> Output {
+        let text = match argv.get(1).map(String::as_str) {
+            Some("-e") => unescape(&argv[2..].join(" ")),
+            _ => argv[1..].join(" "),
+        };
+        text.split('\n')
+            .map(|line| TerminalLine::text("", &format!("  {}", line), false))
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+// backslash escapes for `echo -e` - unknown ones are kept as typed
+fn unescape(text: &str) -> String {
+    const ESCAPES: [(&str, char); 7] =
+        [("e", '\x1b'), ("033", '\x1b'), ("x1b", '\x1b'), ("x1B", '\x1b'), ("n", '\n'), ("t", '\t'), ("\\", '\\')];
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(i) = rest.find('\\') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        match ESCAPES.iter().find(|(seq, _)| rest.starts_with(seq)) {
+            Some((seq, c)) => {
+                out.push(*c);
+                rest = &rest[seq.len()..];
+            }
+            None => out.push('\\'),
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+struct History;
+
+impl Command for History {
+    fn name(&self) -> &'static str { "history" }
+    fn summary(&self) -> &'static str { "Previously entered commands" }
+    fn usage(&self) -> &'static str { "history [-c] [N]" }
+    fn description(&self) -> &'static str {
+        "Lists previously entered command lines, numbered from the oldest. History survives page reloads. !! re-runs the last line, !N line N and !-N the Nth most recent. Up/Down and Ctrl+R browse it from the prompt."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-c, --clear", "Forget all history"),
+            ("N", "Only show the last N entries"),
+        ]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["history 10", "!!", "!3", "history -c"] }
+
+    fn complete(&self, _words: &[String], _registry: &Registry) -> Vec<String> {
+        vec!["-c".into()]
+    }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &[]);
+        if args.has(&["c", "clear"]) {
+            ctx.history.update_value(|h| h.clear());
+            return Output::ok(vec![]);
+        }
+        let limit = match args.positional.first().map(|n| (n, n.parse::<usize>())) {
+            None => usize::MAX,
+            Some((_, Ok(n))) => n,
+            Some((n, Err(_))) => return Output::error(2, &format!("history: {}: numeric argument required", n)),
+        };
+
+        ctx.history.with_value(|h| {
+            let entries = h.entries();
+            let skip = entries.len().saturating_sub(limit);
+            let mut table = Table::new().align(0, Align::Right).min_width(0, 4);
+            for (i, entry) in entries.iter().enumerate().skip(skip) {
+                table.push(vec![(i + 1).to_string().into(), entry.as_str().into()]);
+            }
+            table.render().into()
+        })
+    }
+}
+
+struct ScrollbackLimit;
+
+impl Command for ScrollbackLimit {
+    fn name(&self) -> &'static str { "scrollback" }
+    fn summary(&self) -> &'static str { "Show or set how much output is kept" }
+    fn usage(&self) -> &'static str { "scrollback [N]" }
+    fn description(&self) -> &'static str {
+        "Like HISTSIZE for the screen: the terminal keeps the last N lines of output and drops older ones. With no argument, prints how many lines are kept now and the limit.\nThe limit is saved in the browser and must be between 100 and 100000 (5000 by default)."
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["scrollback", "scrollback 20000"] }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let Some(arg) = argv.get(1) else {
+            let (len, limit) = ctx.scrollback.with_untracked(|h| (h.len(), h.limit()));
+            return vec![TerminalLine::text("", &format!("  {} lines, keeping the last {}", len, limit), false)].into();
+        };
+        match arg.parse::<usize>() {
+            Ok(limit) if (scrollback::MIN_LIMIT..=scrollback::MAX_LIMIT).contains(&limit) => {
+                ctx.set_history.update(|h| h.set_limit(limit));
+                vec![TerminalLine::text("", &format!("  keeping the last {} lines", limit), false)].into()
+            }
+            _ => Output::error(2, &format!(
+                "scrollback: limit must be between {} and {}",
+                scrollback::MIN_LIMIT,
+                scrollback::MAX_LIMIT,
+            )),
+        }
+    }
+}
+
+struct Man;
+
+impl Command for Man {
+    fn name(&self) -> &'static str { "man" }
+    fn summary(&self) -> &'static str { "Manual page for a command" }
+    fn usage(&self) -> &'static str { "man <command>" }
+    fn description(&self) -> &'static str {
+        "Shows the manual page for COMMAND. Pages are generated from the metadata each command is registered with, so they always describe the real command."
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["man grep", "man history"] }
+
+    fn complete(&self, words: &[String], registry: &Registry) -> Vec<String> {
+        if words.len() == 1 { registry.completion_names() } else { vec![] }
+    }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let Some(name) = argv.get(1) else {
+            return Output::error(1, "What manual page do you want? (try: man grep)");
+        };
+        match ctx.registry.find(&name.to_lowercase()) {
+            Some(command) => man_page(command).into(),
+            None => Output::error(16, &format!("No manual entry for {}", name)),
+        }
+    }
+}
+
+// NAME / SYNOPSIS / DESCRIPTION / OPTIONS / EXAMPLES, all from metadata
+fn man_page(command: &dyn Command) -> Vec<TerminalLine> {
+    let mut lines = vec![
+        TerminalLine::text("", "", false),
+        TerminalLine::section("", "  NAME"),
+        TerminalLine::text("", &format!("    {} - {}", command.name(), command.summary()), false),
+        TerminalLine::text("", "", false),
+        TerminalLine::section("", "  SYNOPSIS"),
+        TerminalLine::bold("", &format!("    {}", command.usage())),
+        TerminalLine::text("", "", false),
+        TerminalLine::section("", "  DESCRIPTION"),
+    ];
+    lines.extend(command.description().lines().map(|p| TerminalLine::paragraph("    ", "    ", p)));
+    if !command.aliases().is_empty() {
+        lines.push(TerminalLine::text("", &format!("    Aliases: {}", command.aliases().join(", ")), false));
+    }
+
+    let options = command.options();
+    if !options.is_empty() {
+        lines.push(TerminalLine::text("", "", false));
+        lines.push(TerminalLine::section("", "  OPTIONS"));
+        let mut table = Table::new().indent("    ").gap(3);
+        for (flags, help) in options {
+            table.push(vec![Cell::cmd(flags), (*help).into()]);
+        }
+        lines.extend(table.render());
+    }
+
+    let examples = command.examples();
+    if !examples.is_empty() {
+        lines.push(TerminalLine::text("", "", false));
+        lines.push(TerminalLine::section("", "  EXAMPLES"));
+        lines.extend(examples.iter().map(|e| TerminalLine::text("", &format!("    $ {}", e), false)));
+    }
+    lines.push(TerminalLine::text("", "", false));
+    lines
+}
+
+struct Sync;
+
+impl Command for Sync {
+    fn name(&self) -> &'static str { "sync" }
+    fn summary(&self) -> &'static str { "Show or refresh the content source" }
+    fn usage(&self) -> &'static str { "sync [-f] [url]" }
+    fn description(&self) -> &'static str {
+        "Shows whether projects, skills and the profile come from the content built into the site or from a fetched portfolio.json, and why a fetch was not used.\nThe JSON has the same shape as content/portfolio.toml. A failed fetch or invalid file never replaces working content."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-f, --fetch", "Fetch again now"),
+            ("url", "Fetch from this url instead of the configured one (implies -f)"),
+        ]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["sync", "sync -f"] }
+
+    fn complete(&self, words: &[String], _registry: &Registry) -> Vec<String> {
+        if words.len() == 1 { vec!["-f".into()] } else { vec![] }
+    }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &[]);
+        let url = args.positional.first().cloned();
+        if !args.has(&["f", "fetch"]) && url.is_none() {
+            return sync_status().into();
+        }
+
+        let Some(url) = url.or_else(|| content::REMOTE_URL.map(str::to_string)) else {
+            return Output::new(2, vec![
+                TerminalLine::text("", "  sync: no url configured - this build was made without PORTFOLIO_URL", false),
+                TerminalLine::text("", &format!("  usage: {}", self.usage()), false),
+            ]);
+        };
+        let fetching = TerminalLine::text("", &format!("  fetching {}...", url), false);
+        let set_history = ctx.set_history;
+        spawn_local(async move {
+            let line = match content::refresh(&url).await {
+                Ok(()) => format!("  [ok] content loaded from {}", url),
+                Err(err) => format!("  [!!] {}: {} - keeping current content", url, err),
+            };
+            set_history.update(|h| h.push(TerminalLine::text("", &line, false)));
+        });
+        vec![fetching].into()
+    }
+}
+
+fn sync_status() -> Vec<TerminalLine> {
+    let (source, last_error) = content::source();
+    let portfolio = portfolio();
+    let mut table = Table::new().indent("    ");
+    match source {
+        Source::BuiltIn => table.push(vec![Cell::cmd("source"), "built-in (compiled from content/portfolio.toml)".into()]),
+        Source::Remote { url, fetched_at } => {
+            let age = ((js_sys::Date::now() - fetched_at) / 1000.0) as u64;
+            table.push(vec![Cell::cmd("source"), format!("remote {} (fetched {}s ago)", url, age).into()]);
+        }
+    }
+    if let Some(err) = last_error {
+        table.push(vec![Cell::cmd("last try"), format!("failed: {}", err).into()]);
+    }
+    table.push(vec![
+        Cell::cmd("content"),
+        format!("{} projects, {} skill areas", portfolio.projects.len(), portfolio.skills.len()).into(),
+    ]);
+    let mut lines = vec![TerminalLine::text("", "", false)];
+    lines.extend(table.render());
+    lines.push(TerminalLine::text("", "", false));
+    lines
+}
+
+// easter eggs
+
+struct Neofetch;
+
+impl Command for Neofetch {
+    fn name(&self) -> &'static str { "neofetch" }
+    fn summary(&self) -> &'static str { "System info display" }
+    fn usage(&self) -> &'static str { "neofetch" }
+    fn category(&self) -> Category { Category::EasterEgg }
+
+    fn run(&self, _argv: &[String], ctx: &Context) -> Output {
+        let now = js_sys::Date::now();
+        let uptime_ms = now - ctx.start_time;
+        let minutes = (uptime_ms / 60000.0).floor();
+        let seconds = ((uptime_ms % 60000.0) / 1000.0).floor();
+
+        let uptime = format!("{}m {}s", minutes, seconds);
+        let info = |key: &str, value: &str| Cell::parts(vec![OutputPart::Bold(format!("{}: ", key)), OutputPart::Text(value.to_string())]);
+        let mut lines = vec![TerminalLine::text("", "", false)];
+        lines.extend(
+            Table::new()
+                .indent("     ")
+                .gap(6)
+                .align(0, Align::Center)
+                .row(vec!["/\\".into(), Cell::bold("cm_mongo@vanguard")])
+                .row(vec!["/  \\".into(), info("os", "vanguardos 2.0")])
+                .row(vec!["/ 🦀 \\".into(), info("kernel", "rust + wasm")])
+                .row(vec!["/______\\".into(), info("shell", "leptos")])
+                .row(vec!["".into(), info("uptime", &uptime)])
+                .render(),
+        );
+        lines.push(TerminalLine::text("", "", false));
+        lines.into()
+    }
+}
+
+struct Whoami;
+
+impl Command for Whoami {
+    fn name(&self) -> &'static str { "whoami" }
+    fn summary(&self) -> &'static str { "Who am I?" }
+    fn usage(&self) -> &'static str { "whoami" }
+    fn category(&self) -> Category { Category::EasterEgg }
+
+    fn run(&self, _argv: &[String], _ctx: &Context) -> Output {
+        vec![TerminalLine::text("", "  vanguard", false)].into()
+    }
+}
+
+struct Sudo;
+
+impl Command for Sudo {
+    fn name(&self) -> &'static str { "sudo" }
+    fn aliases(&self) -> &'static [&'static str] { &["hire"] }
+    fn summary(&self) -> &'static str { "The magic words" }
+    fn usage(&self) -> &'static str { "sudo hire me" }
+    fn category(&self) -> Category { Category::EasterEgg }
+    fn examples(&self) -> &'static [&'static str] { &["sudo hire me"] }
+
+    fn complete(&self, words: &[String], _registry: &Registry) -> Vec<String> {
+        match words {
+            [_] => vec!["hire".into()],
+            [_, hire] if hire.eq_ignore_ascii_case("hire") => vec!["me".into()],
+            _ => vec![],
+        }
+    }
+
+    fn run(&self, argv: &[String], _ctx: &Context) -> Output {
+        let words: Vec<String> = argv.iter().map(|a| a.to_lowercase()).collect();
+        if words[0] != "hire" && words[1..] != ["hire", "me"] {
+            return Output::error(1, "try: sudo hire me");
+        }
+        vec![
+            TerminalLine::text("", "", false),
+            TerminalLine::text("", "  [sudo] password for visitor: **********", false),
+            TerminalLine::text("", "  authenticating...", false),
+            TerminalLine::text("", "  ACCESS GRANTED.", false),
+            TerminalLine::text("", "", false),
+            TerminalLine::text("", "  initiating onboarding_sequence.sh...", false),
+            TerminalLine::text("", "  > Priority connection established.", false),
+            TerminalLine::text("", "  > Ready to architect your next system.", false),
+            TerminalLine::text("", "", false),
+            TerminalLine::with_link("", "  [ EXECUTE OFFER ] ", "Send Email", &format!("mailto:{}?subject=Priority%20Job%20Offer", portfolio().contact.email), ""),
+            TerminalLine::text("", "", false),
+        ]
+        .into()
+    }
+}
+
+struct Ping;
+
+impl Command for Ping {
+    fn name(&self) -> &'static str { "ping" }
+    fn summary(&self) -> &'static str { "Real-time network latency" }
+    fn usage(&self) -> &'static str { "ping [-c count]" }
+    fn description(&self) -> &'static str {
+        "Measures round-trip time to the site origin with a real HTTP request."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-c, --count N", "Number of requests to send (1-10)"),
+        ]
+    }
+    fn category(&self) -> Category { Category::EasterEgg }
+    fn examples(&self) -> &'static [&'static str] { &["ping", "ping -c 5"] }
+
+    fn complete(&self, words: &[String], _registry: &Registry) -> Vec<String> {
+        if words.len() == 1 { vec!["-c".into()] } else { vec![] }
+    }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &["c", "count"]);
+        let usage_error = |msg: &str| Output::new(2, vec![
+            TerminalLine::text("", &format!("  ping: {}", msg), false),
+            TerminalLine::text("", &format!("  usage: {}", self.usage()), false),
+        ]);
+        let count = match args.value(&["c", "count"]) {
+            Err(err) => return usage_error(&err.to_string()),
+            Ok(None) => 1,
+            Ok(Some(raw)) => match raw.parse::<u32>() {
+                Ok(count @ 1..=10) => count,
+                _ => return usage_error("invalid count (1-10)"),
+            },
+        };
+
+        let set_history = ctx.set_history;
+        spawn_local(async move {
+            let mut total = 0.0;
+            for _ in 0..count {
+                let start = js_sys::Date::now();
+                // Fetch current page just to measure round trip to origin/cache
+                let _ = gloo_net::http::Request::get("/").send().await;
+                let end = js_sys::Date::now();
+                let duration = end - start;
+                total += duration;
+
+                set_history.update(|h| {
+                    h.push(TerminalLine::text("", &format!("  pong. {:.0}ms", duration), false));
+                });
+            }
+            if count > 1 {
+                let avg = total / count as f64;
+                set_history.update(|h| {
+                    h.push(TerminalLine::text("", &format!("  {} packets, avg {:.0}ms", count, avg), false));
+                });
+            }
+        });
+        vec![TerminalLine::text("", "  pinging...", false)].into()
+    }
+}
+
+struct Date;
+
+impl Command for Date {
+    fn name(&self) -> &'static str { "date" }
+    fn summary(&self) -> &'static str { "Print the date" }
+    fn usage(&self) -> &'static str { "date" }
+    fn category(&self) -> Category { Category::EasterEgg }
+
+    fn run(&self, _argv: &[String], _ctx: &Context) -> Output {
+        vec![TerminalLine::text("", "  2026. building the future.", false)].into()
+    }
+}
+
+// hidden - not advertised anywhere
+
+struct Uptime;
+
+impl Command for Uptime {
+    fn name(&self) -> &'static str { "uptime" }
+    fn summary(&self) -> &'static str { "How long the system has been running" }
+    fn usage(&self) -> &'static str { "uptime" }
+    fn category(&self) -> Category { Category::Hidden }
+
+    fn run(&self, _argv: &[String], _ctx: &Context) -> Output {
+        vec![TerminalLine::text("", "  up 1337 days, building systems", false)].into()
+    }
+}
+
+struct Reboot;
+
+impl Command for Reboot {
+    fn name(&self) -> &'static str { "reboot" }
+    fn summary(&self) -> &'static str { "Replay the boot sequence" }
+    fn usage(&self) -> &'static str { "reboot" }
+    fn description(&self) -> &'static str {
+        "Clears the screen and plays the boot sequence again. Output from earlier commands on the same line is dropped and nothing after it runs. Any key or a click skips to the prompt; with reduced motion enabled it appears at once."
+    }
+    fn category(&self) -> Category { Category::Utility }
+
+    fn run(&self, _argv: &[String], _ctx: &Context) -> Output {
+        Output { reboot: true, ..Output::cleared(vec![]) }
+    }
+}
+
+struct Exit;
+
+impl Command for Exit {
+    fn name(&self) -> &'static str { "exit" }
+    fn aliases(&self) -> &'static [&'static str] { &["quit"] }
+    fn summary(&self) -> &'static str { "Leave the terminal" }
+    fn usage(&self) -> &'static str { "exit" }
+    fn category(&self) -> Category { Category::Hidden }
+
+    fn run(&self, _argv: &[String], _ctx: &Context) -> Output {
+        vec![TerminalLine::text("", "  no escape. type 'help' :)", false)].into()
+    }
+}
+
+struct Rust;
+
+impl Command for Rust {
+    fn name(&self) -> &'static str { "rust" }
+    fn aliases(&self) -> &'static [&'static str] { &["🦀"] }
+    fn summary(&self) -> &'static str { "btw" }
+    fn usage(&self) -> &'static str { "rust" }
+    fn category(&self) -> Category { Category::Hidden }
+
+    fn run(&self, _argv: &[String], _ctx: &Context) -> Output {
+        vec![TerminalLine::text("", "  🦀 btw i use rust", false)].into()
+    }
+}
+
+struct True;
+
+impl Command for True {
+    fn name(&self) -> &'static str { "true" }
+    fn summary(&self) -> &'static str { "Do nothing, successfully" }
+    fn usage(&self) -> &'static str { "true" }
+    fn category(&self) -> Category { Category::Hidden }
+
+    fn run(&self, _argv: &[String], _ctx: &Context) -> Output {
+        Output::ok(vec![])
+    }
+}
+
+struct False;
+
+impl Command for False {
+    fn name(&self) -> &'static str { "false" }
+    fn summary(&self) -> &'static str { "Do nothing, unsuccessfully" }
+    fn usage(&self) -> &'static str { "false" }
+    fn category(&self) -> Category { Category::Hidden }
+
+    fn run(&self, _argv: &[String], _ctx: &Context) -> Output {
+        Output::new(1, vec![])
+    }
+}
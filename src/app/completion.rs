@@ -0,0 +1,83 @@
+use super::commands::Registry;
+use super::tokenizer::{quote, tokenize, Token};
+use super::vfs::complete_path;
+
+// result of pressing Tab on a line
+pub(super) struct Completion {
+    // the line with the current word completed as far as it is unambiguous
+    pub(super) line: String,
+    // every match when there is more than one, listed on a second Tab
+    pub(super) candidates: Vec<String>,
+}
+
+// complete the word under the caret (always the end of the line here)
+// the first word of a command completes to command names, later words
+// ask the command itself via `Command::complete`
+pub(super) fn complete(registry: &Registry, line: &str, cwd: &str) -> Completion {
+    let start = line
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace() || matches!(c, '|' | ';' | '&' | '>'))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let (head, prefix) = line.split_at(start);
+    let unchanged = || Completion { line: line.to_string(), candidates: vec![] };
+
+    // words already typed for the command being completed - only the part
+    // after the last `|`, `;`, `&&` or `||` matters, minus any `> file`
+    let Ok(tokens) = tokenize(head) else {
+        return unchanged();
+    };
+    let stage = tokens
+        .rsplit(|t| matches!(t, Token::Pipe | Token::Semi | Token::And | Token::Or))
+        .next()
+        .unwrap_or_default();
+    let mut words: Vec<String> = vec![];
+    let mut target = false;
+    for token in stage {
+        match token {
+            Token::Redirect { .. } => target = true,
+            Token::Word(_) if target => target = false,
+            Token::Word(word) => words.push(word.expand(|_| None)),
+            _ => {}
+        }
+    }
+
+    let options = match words.first().and_then(|name| registry.find(&name.to_lowercase())) {
+        // the word after `>` is always a file
+        _ if target => complete_path(cwd, prefix),
+        None if words.is_empty() => registry.completion_names(),
+        None => vec![],
+        Some(command) => {
+            let mut options = command.complete(&words, registry);
+            if command.takes_paths() && !prefix.starts_with('-') {
+                options.extend(complete_path(cwd, prefix));
+            }
+            options
+        }
+    };
+
+    let lower = prefix.to_lowercase();
+    let mut matches: Vec<String> = options
+        .into_iter()
+        .filter(|option| option.to_lowercase().starts_with(&lower))
+        .collect();
+    matches.sort();
+    matches.dedup();
+
+    match matches.as_slice() {
+        [] => unchanged(),
+        // a directory stays open for the next path segment
+        [only] if only.ends_with('/') => Completion { line: format!("{}{}", head, quote(only)), candidates: vec![] },
+        [only] => Completion { line: format!("{}{} ", head, quote(only)), candidates: vec![] },
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.clone(), |acc, m| common_prefix(&acc, m));
+            // keep what was typed if the matches only agree case-insensitively
+            let common = if common.chars().count() < prefix.chars().count() { prefix.to_string() } else { common };
+            Completion { line: format!("{}{}", head, common), candidates: matches }
+        }
+    }
+}
+
+fn common_prefix(a: &str, b: &str) -> String {
+    a.chars().zip(b.chars()).take_while(|(x, y)| x == y).map(|(x, _)| x).collect()
+}
@@ -0,0 +1,80 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use super::portfolio::Portfolio;
+
+// content/portfolio.toml - checked against the schema and turned into JSON by build.rs
+const EMBEDDED: &str = include_str!(concat!(env!("OUT_DIR"), "/portfolio.json"));
+
+// where fresher content is fetched from - same shape as portfolio.toml, as JSON.
+// set at build time with PORTFOLIO_URL (relative urls resolve against the page).
+// nothing publishes a portfolio.json by default, so without it nothing is fetched
+pub(super) const REMOTE_URL: Option<&str> = option_env!("PORTFOLIO_URL");
+
+#[derive(Clone, PartialEq, Debug)]
+pub(super) enum Source {
+    BuiltIn,
+    // fetched_at is a js timestamp in ms
+    Remote { url: String, fetched_at: f64 },
+}
+
+// the portfolio every view renders from - the compiled-in copy until a
+// fetched one replaces it
+struct Content {
+    portfolio: Rc<Portfolio>,
+    source: Source,
+    // why the last fetch was not used, None if it was (or none ran yet)
+    last_error: Option<String>,
+}
+
+thread_local! {
+    static CONTENT: RefCell<Content> = RefCell::new(Content {
+        portfolio: Rc::new(parse(EMBEDDED).expect("content/portfolio.toml is validated by build.rs")),
+        source: Source::BuiltIn,
+        last_error: None,
+    });
+}
+
+pub(super) fn portfolio() -> Rc<Portfolio> {
+    CONTENT.with(|c| c.borrow().portfolio.clone())
+}
+
+pub(super) fn source() -> (Source, Option<String>) {
+    CONTENT.with(|c| {
+        let c = c.borrow();
+        (c.source.clone(), c.last_error.clone())
+    })
+}
+
+fn parse(json: &str) -> Result<Portfolio, String> {
+    let portfolio: Portfolio = serde_json::from_str(json).map_err(|err| format!("bad JSON: {}", err))?;
+    portfolio.validate().map_err(|errors| errors.join("; "))?;
+    Ok(portfolio)
+}
+
+// fetch `url` and swap it in - on any failure the current content stays
+// and the reason is kept for `sync`
+pub(super) async fn refresh(url: &str) -> Result<(), String> {
+    let result = fetch(url).await;
+    CONTENT.with(|c| {
+        let mut c = c.borrow_mut();
+        match &result {
+            Ok(portfolio) => {
+                c.portfolio = Rc::new(portfolio.clone());
+                c.source = Source::Remote { url: url.to_string(), fetched_at: js_sys::Date::now() };
+                c.last_error = None;
+            }
+            Err(err) => c.last_error = Some(err.clone()),
+        }
+    });
+    result.map(|_| ())
+}
+
+async fn fetch(url: &str) -> Result<Portfolio, String> {
+    let response = gloo_net::http::Request::get(url).send().await.map_err(|err| err.to_string())?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    let body = response.text().await.map_err(|err| err.to_string())?;
+    parse(&body)
+}
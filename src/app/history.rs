@@ -0,0 +1,189 @@
+use super::storage;
+
+const STORAGE_KEY: &str = "vanguard.history";
+// like HISTFILESIZE - oldest entries fall off the front
+const CAPACITY: usize = 500;
+
+// previously entered command lines, kept apart from the rendered output
+// so Up/Down and `history` only ever see what the visitor typed
+#[derive(Default)]
+pub(super) struct CommandHistory {
+    entries: Vec<String>,
+    // index into entries while browsing with Up/Down, None when not browsing
+    cursor: Option<usize>,
+    // what was on the line before browsing started, restored past the newest entry
+    draft: String,
+}
+
+impl CommandHistory {
+    // restore from localStorage, empty if storage is unavailable
+    pub(super) fn load() -> Self {
+        let entries = storage()
+            .and_then(|s| s.get_item(STORAGE_KEY).ok().flatten())
+            .map(|raw| raw.lines().map(String::from).collect())
+            .unwrap_or_default();
+        Self { entries, ..Self::default() }
+    }
+
+    fn save(&self) {
+        if let Some(storage) = storage() {
+            let _ = storage.set_item(STORAGE_KEY, &self.entries.join("\n"));
+        }
+    }
+
+    pub(super) fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    // leave Up/Down browsing, the next Up starts from the newest entry again
+    pub(super) fn stop_browsing(&mut self) {
+        self.cursor = None;
+        self.draft.clear();
+    }
+
+    // record a submitted line - blanks and immediate repeats are skipped
+    pub(super) fn push(&mut self, line: &str) {
+        self.stop_browsing();
+        let line = line.trim();
+        if line.is_empty() || self.entries.last().map(String::as_str) == Some(line) {
+            return;
+        }
+        self.entries.push(line.to_string());
+        if self.entries.len() > CAPACITY {
+            self.entries.drain(..self.entries.len() - CAPACITY);
+        }
+        self.save();
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.entries.clear();
+        self.cursor = None;
+        self.save();
+    }
+
+    // Up - one entry older, `current` is stashed when browsing starts
+    pub(super) fn prev(&mut self, current: &str) -> Option<String> {
+        let index = match self.cursor {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(i) => i.saturating_sub(1),
+        };
+        self.cursor = Some(index);
+        Some(self.entries[index].clone())
+    }
+
+    // Down - one entry newer, then back to the stashed line
+    pub(super) fn next(&mut self) -> Option<String> {
+        let index = self.cursor?;
+        if index + 1 < self.entries.len() {
+            self.cursor = Some(index + 1);
+            Some(self.entries[index + 1].clone())
+        } else {
+            self.cursor = None;
+            Some(std::mem::take(&mut self.draft))
+        }
+    }
+
+    // bash-style history expansion: `!!` is the last line, `!n` entry n
+    // (1-based, as `history` numbers them) and `!-n` the nth most recent.
+    // nothing is expanded inside single quotes.
+    pub(super) fn expand(&self, line: &str) -> Result<String, String> {
+        let mut out = String::new();
+        let mut in_single = false;
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                in_single = !in_single;
+            }
+            if c != '!' || in_single {
+                out.push(c);
+                continue;
+            }
+            let (event, entry) = match chars.peek() {
+                Some('!') => {
+                    chars.next();
+                    ("!!".to_string(), self.entries.last())
+                }
+                Some(c) if c.is_ascii_digit() || *c == '-' => {
+                    let mut digits = String::new();
+                    if chars.next_if_eq(&'-').is_some() {
+                        digits.push('-');
+                    }
+                    while let Some(d) = chars.next_if(|d| d.is_ascii_digit()) {
+                        digits.push(d);
+                    }
+                    let entry = match digits.parse::<isize>() {
+                        Ok(n) if n > 0 => self.entries.get(n as usize - 1),
+                        Ok(n) if n < 0 => self.entries.len().checked_sub(n.unsigned_abs()).and_then(|i| self.entries.get(i)),
+                        _ => None,
+                    };
+                    (format!("!{}", digits), entry)
+                }
+                // a lone `!` is just a character
+                _ => {
+                    out.push('!');
+                    continue;
+                }
+            };
+            match entry {
+                Some(entry) => out.push_str(entry),
+                None => return Err(format!("{}: event not found", event)),
+            }
+        }
+        Ok(out)
+    }
+}
+
+// bash's Ctrl+R `(reverse-i-search)` state
+// typed characters go into `query`, the matched entry is what the input shows
+#[derive(Clone, PartialEq, Default)]
+pub(super) struct ReverseSearch {
+    pub(super) query: String,
+    // index of the current match in the history ring
+    found: Option<usize>,
+    // the line being edited when the search started, restored on cancel
+    pub(super) original: String,
+    // nothing (older) matches the query
+    pub(super) failed: bool,
+}
+
+impl ReverseSearch {
+    pub(super) fn start(original: String) -> Self {
+        Self { original, ..Self::default() }
+    }
+
+    // the entry the search currently points at
+    pub(super) fn matched(&self, history: &CommandHistory) -> Option<String> {
+        self.found.and_then(|i| history.entries.get(i)).cloned()
+    }
+
+    // the query changed - search again from the newest entry
+    pub(super) fn set_query(&mut self, history: &CommandHistory, query: String) {
+        self.query = query;
+        self.search(history, history.entries.len());
+    }
+
+    // Ctrl+R again - the next older match, staying put if there is none
+    pub(super) fn next(&mut self, history: &CommandHistory) {
+        let before = self.found.unwrap_or(history.entries.len());
+        self.search(history, before);
+    }
+
+    fn search(&mut self, history: &CommandHistory, before: usize) {
+        if self.query.is_empty() {
+            self.failed = false;
+            return;
+        }
+        match history.entries[..before].iter().rposition(|e| e.contains(&self.query)) {
+            Some(i) => {
+                self.found = Some(i);
+                self.failed = false;
+            }
+            None => self.failed = true,
+        }
+    }
+}
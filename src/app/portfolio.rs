@@ -0,0 +1,242 @@
+// portfolio content schema - content/portfolio.toml is deserialized into
+// these types. build.rs includes this file too, so the build fails on
+// exactly the same rules the terminal relies on at runtime.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Portfolio {
+    pub(super) profile: Profile,
+    pub(super) contact: Contact,
+    pub(super) skills: Vec<SkillArea>,
+    pub(super) projects: Vec<Project>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Profile {
+    pub(super) name: String,
+    // boot screen banner and the line under it
+    pub(super) banner: String,
+    pub(super) tagline: String,
+    // headline under the name in `about`
+    pub(super) title: String,
+    pub(super) focus: Vec<String>,
+    pub(super) expertise: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Contact {
+    pub(super) email: String,
+    // github user name, the profile url is derived from it
+    pub(super) github: String,
+}
+
+impl Contact {
+    pub(super) fn github_url(&self) -> String {
+        format!("https://github.com/{}", self.github)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct SkillArea {
+    pub(super) area: String,
+    pub(super) items: Vec<Skill>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Skill {
+    pub(super) name: String,
+    // self-assessed, 1-10 - left out until the owner has given one
+    #[serde(default)]
+    pub(super) level: Option<u8>,
+    #[serde(default)]
+    pub(super) years: Option<u8>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) enum Domain {
+    IndustrialEdge,
+    Systems,
+    FullStack,
+    WasmTools,
+}
+
+impl Domain {
+    // listing order of `projects`
+    pub(super) const ALL: [Domain; 4] = [Domain::IndustrialEdge, Domain::Systems, Domain::FullStack, Domain::WasmTools];
+
+    // the name used in portfolio.toml and by `projects --category`
+    pub(super) fn slug(self) -> &'static str {
+        match self {
+            Domain::IndustrialEdge => "industrial-edge",
+            Domain::Systems => "systems",
+            Domain::FullStack => "full-stack",
+            Domain::WasmTools => "wasm-tools",
+        }
+    }
+
+    // `edge`, `wasm-tools` and `architecture` all pick a domain - any word
+    // of the slug, or part of the title, case-insensitive. an empty query
+    // matches nothing rather than whichever domain comes first
+    pub(super) fn matches(self, query: &str) -> bool {
+        if query.is_empty() {
+            return false;
+        }
+        let query = query.to_lowercase();
+        self.slug() == query || self.slug().split('-').any(|word| word == query) || self.title().to_lowercase().contains(&query)
+    }
+
+    pub(super) fn title(self) -> &'static str {
+        match self {
+            Domain::IndustrialEdge => "INDUSTRIAL EDGE",
+            Domain::Systems => "SYSTEMS ARCHITECTURE",
+            Domain::FullStack => "FULL STACK",
+            Domain::WasmTools => "WASM & TOOLS",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Project {
+    pub(super) title: String,
+    // one-line pitch shown under the title
+    pub(super) description: String,
+    #[serde(default)]
+    pub(super) tags: Vec<String>,
+    pub(super) category: Domain,
+    // short numbers and facts, joined with • in listings
+    #[serde(default)]
+    pub(super) metrics: Vec<String>,
+    // the first highlight is the blurb `about` uses for flagship work
+    #[serde(default)]
+    pub(super) highlights: Vec<String>,
+    // how it is built, for the `project <name>` card
+    #[serde(default)]
+    pub(super) architecture: Vec<String>,
+    #[serde(default)]
+    pub(super) demo_url: Option<String>,
+    // "YYYY-MM" of the latest significant work, for `projects --sort date`
+    #[serde(default)]
+    pub(super) date: Option<String>,
+    pub(super) github_url: String,
+    // listed under FLAGSHIP WORK in `about`
+    #[serde(default)]
+    pub(super) flagship: bool,
+}
+
+impl Project {
+    // "Guardian One Platform" -> "guardian-one-platform"
+    pub(super) fn slug(&self) -> String {
+        slugify(&self.title)
+    }
+
+    // repository name, which doubles as the directory name in `ls`
+    pub(super) fn repo(&self) -> &str {
+        self.github_url.rsplit('/').next().unwrap_or(&self.title)
+    }
+}
+
+// lowercase words joined by dashes, for names typed at the prompt
+pub(super) fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+impl Portfolio {
+    // case-insensitive lookup, with the area the skill is listed under
+    pub(super) fn skill(&self, name: &str) -> Option<(&SkillArea, &Skill)> {
+        self.skills
+            .iter()
+            .flat_map(|area| area.items.iter().map(move |skill| (area, skill)))
+            .find(|(_, skill)| skill.name.eq_ignore_ascii_case(name))
+    }
+
+    // rules serde can't express - every problem is reported, not just the first
+    pub(super) fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = vec![];
+        let mut require = |ok: bool, what: String| {
+            if !ok {
+                errors.push(what);
+            }
+        };
+
+        require(!self.profile.name.trim().is_empty(), "profile.name is empty".into());
+        require(!self.profile.banner.trim().is_empty(), "profile.banner is empty".into());
+        require(self.contact.email.contains('@'), format!("contact.email `{}` is not an email address", self.contact.email));
+        require(
+            !self.contact.github.is_empty() && !self.contact.github.contains('/'),
+            format!("contact.github `{}` should be a user name, not a url", self.contact.github),
+        );
+
+        let skills: Vec<&Skill> = self.skills.iter().flat_map(|a| &a.items).collect();
+        for (i, skill) in skills.iter().enumerate() {
+            require(
+                !skills[..i].iter().any(|s| s.name.eq_ignore_ascii_case(&skill.name)),
+                format!("skill `{}` is listed twice", skill.name),
+            );
+        }
+
+        for (i, area) in self.skills.iter().enumerate() {
+            require(!area.area.trim().is_empty(), format!("skills[{}]: area is empty", i));
+            require(!area.items.is_empty(), format!("skills[{}] `{}`: no items", i, area.area));
+            for skill in &area.items {
+                if let Some(level) = skill.level {
+                    require((1..=10).contains(&level), format!("skills[{}] `{}`: level {} is not 1-10", i, skill.name, level));
+                }
+                if let Some(years) = skill.years {
+                    require(years <= 50, format!("skills[{}] `{}`: {} years is not plausible", i, skill.name, years));
+                }
+            }
+        }
+
+        require(!self.projects.is_empty(), "no projects".into());
+        for (i, project) in self.projects.iter().enumerate() {
+            let at = format!("projects[{}] `{}`", i, project.title);
+            require(!project.title.trim().is_empty(), format!("projects[{}]: title is empty", i));
+            require(!project.description.trim().is_empty(), format!("{}: description is empty", at));
+            require(
+                project.github_url.starts_with("https://github.com/") && !project.repo().is_empty(),
+                format!("{}: github_url `{}` is not a https://github.com/ repository", at, project.github_url),
+            );
+            // tags are the shared vocabulary with skills, `skills <tag>` finds the project
+            for tag in &project.tags {
+                require(
+                    skills.iter().any(|s| s.name == *tag),
+                    format!("{}: tag `{}` is not a skill - add it to [[skills]] or match its spelling", at, tag),
+                );
+            }
+            if let Some(date) = &project.date {
+                let valid = matches!(date.split_once('-'), Some((y, m))
+                    if y.len() == 4 && y.chars().all(|c| c.is_ascii_digit()) && matches!(m.parse::<u8>(), Ok(1..=12)) && m.len() == 2);
+                require(valid, format!("{}: date `{}` should be YYYY-MM", at, date));
+            }
+            if let Some(url) = &project.demo_url {
+                require(url.starts_with("https://"), format!("{}: demo_url `{}` must be https", at, url));
+            }
+            require(
+                !self.projects[..i].iter().any(|p| p.slug() == project.slug()),
+                format!("{}: duplicate title", at),
+            );
+            require(
+                !self.projects[..i].iter().any(|p| p.repo().eq_ignore_ascii_case(project.repo())),
+                format!("{}: repository `{}` is listed twice", at, project.repo()),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// readline-style editing of the input line
+// the caret is a char index - the DOM's UTF-16 offsets are converted at the edges
+
+#[derive(Clone, PartialEq, Debug)]
+pub(super) struct EditLine {
+    chars: Vec<char>,
+    pub(super) caret: usize,
+}
+
+impl EditLine {
+    // from the input's value and its UTF-16 selection start
+    pub(super) fn from_dom(text: &str, caret_utf16: u32) -> Self {
+        let mut units = 0;
+        let caret = text
+            .chars()
+            .take_while(|c| {
+                units += c.len_utf16() as u32;
+                units <= caret_utf16
+            })
+            .count();
+        Self { chars: text.chars().collect(), caret }
+    }
+
+    pub(super) fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    // caret as a UTF-16 offset for `set_selection_range`
+    pub(super) fn caret_utf16(&self) -> u32 {
+        self.chars[..self.caret].iter().map(|c| c.len_utf16() as u32).sum()
+    }
+
+    // Ctrl+A
+    pub(super) fn start_of_line(&mut self) {
+        self.caret = 0;
+    }
+
+    // Ctrl+E
+    pub(super) fn end_of_line(&mut self) {
+        self.caret = self.chars.len();
+    }
+
+    // Ctrl+U
+    pub(super) fn kill_to_start(&mut self) {
+        self.chars.drain(..self.caret);
+        self.caret = 0;
+    }
+
+    // Ctrl+K
+    pub(super) fn kill_to_end(&mut self) {
+        self.chars.truncate(self.caret);
+    }
+
+    // Ctrl+W - the whitespace-delimited word before the caret
+    pub(super) fn kill_word_back(&mut self) {
+        let mut start = self.caret;
+        while start > 0 && self.chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        while start > 0 && !self.chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        self.chars.drain(start..self.caret);
+        self.caret = start;
+    }
+
+    // Alt+B - to the start of this or the previous alphanumeric word
+    pub(super) fn word_back(&mut self) {
+        while self.caret > 0 && !self.chars[self.caret - 1].is_alphanumeric() {
+            self.caret -= 1;
+        }
+        while self.caret > 0 && self.chars[self.caret - 1].is_alphanumeric() {
+            self.caret -= 1;
+        }
+    }
+
+    // Alt+F - to the end of this or the next alphanumeric word
+    pub(super) fn word_forward(&mut self) {
+        let len = self.chars.len();
+        while self.caret < len && !self.chars[self.caret].is_alphanumeric() {
+            self.caret += 1;
+        }
+        while self.caret < len && self.chars[self.caret].is_alphanumeric() {
+            self.caret += 1;
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// terminal geometry - how many columns of output fit in `terminal-body`,
+// plus the width-dependent text (dividers, wrapped paragraphs) built on it
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use leptos::window;
+use wasm_bindgen::JsCast;
+
+use super::table::{char_width, width};
+
+// until the terminal has been measured, and for anything narrower
+const DEFAULT_COLUMNS: usize = 80;
+const MIN_COLUMNS: usize = 20;
+// characters in the probe span - more of them average out subpixel widths
+const PROBE: usize = 100;
+
+static COLUMNS: AtomicUsize = AtomicUsize::new(DEFAULT_COLUMNS);
+
+// the last measured width, what `$COLUMNS` and `tput cols` report
+pub(super) fn columns() -> usize {
+    COLUMNS.load(Ordering::Relaxed)
+}
+
+// measure `body` and remember the result - returns true if it changed
+pub(super) fn remeasure(body: &web_sys::HtmlElement) -> bool {
+    let Some(columns) = measure(body) else {
+        return false;
+    };
+    COLUMNS.swap(columns, Ordering::Relaxed) != columns
+}
+
+// content width over the width of one output character. the probe sits in
+// a `terminal-line` so it gets the same font as output, including the
+// smaller mobile size
+fn measure(body: &web_sys::HtmlElement) -> Option<usize> {
+    let document = body.owner_document()?;
+    let line = document.create_element("div").ok()?;
+    line.set_class_name("terminal-line");
+    let probe = document.create_element("span").ok()?;
+    probe.set_class_name("output");
+    probe.set_attribute("style", "position: absolute; visibility: hidden; white-space: pre;").ok()?;
+    probe.set_text_content(Some(&"0".repeat(PROBE)));
+    line.append_child(&probe).ok()?;
+    body.append_child(&line).ok()?;
+    let glyph = probe.get_bounding_client_rect().width() / PROBE as f64;
+    let _ = body.remove_child(&line);
+
+    let style = window().get_computed_style(body.unchecked_ref()).ok()??;
+    let padding = |side: &str| {
+        let value = style.get_property_value(side).unwrap_or_default();
+        value.trim_end_matches("px").parse::<f64>().unwrap_or(0.0)
+    };
+    let inner = body.client_width() as f64 - padding("padding-left") - padding("padding-right");
+    (glyph > 0.0).then(|| ((inner / glyph) as usize).max(MIN_COLUMNS))
+}
+
+// `fill` repeated to `columns` display columns, e.g. ("─", 4) -> "────".
+// patterns like "- " cycle and never leave a trailing space
+pub(super) fn rule(fill: &str, columns: usize) -> String {
+    let mut out = String::new();
+    let mut used = 0;
+    for c in fill.chars().cycle() {
+        let w = char_width(c);
+        if used + w > columns || w == 0 {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.trim_end().to_string()
+}
+
+// greedy word wrap - the first row has `first` columns, later rows `rest`.
+// words longer than a row are split wherever they run out of room
+pub(super) fn wrap(text: &str, first: usize, rest: usize) -> Vec<String> {
+    let mut rows = vec![];
+    let mut row = String::new();
+    let mut used = 0;
+    let room = |rows: &Vec<String>| if rows.is_empty() { first } else { rest }.max(1);
+    for word in text.split(' ').filter(|word| !word.is_empty()) {
+        let w = width(word);
+        if used > 0 && used + 1 + w <= room(&rows) {
+            row.push(' ');
+            row.push_str(word);
+            used += 1 + w;
+            continue;
+        }
+        if used > 0 {
+            rows.push(std::mem::take(&mut row));
+            used = 0;
+        }
+        for c in word.chars() {
+            let w = char_width(c);
+            if used > 0 && used + w > room(&rows) {
+                rows.push(std::mem::take(&mut row));
+                used = 0;
+            }
+            row.push(c);
+            used += w;
+        }
+    }
+    if !row.is_empty() || rows.is_empty() {
+        rows.push(row);
+    }
+    rows
+}
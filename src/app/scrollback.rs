@@ -0,0 +1,370 @@
+// terminal output - capped like a real terminal's scrollback so a long
+// session cannot grow without bound, and windowed so only the lines in view
+// (plus a margin) are mounted. heights are measured as lines come into
+// view, lines never seen are assumed to be as tall as the average seen so far.
+// measured heights also go into prefix sums, so finding the lines in view
+// costs the same at 50k lines as at 50
+
+use std::collections::VecDeque;
+
+use super::{storage, TerminalLine};
+
+const STORAGE_KEY: &str = "vanguard.scrollback";
+// like HISTSIZE for the screen - oldest lines fall off the front
+const DEFAULT_LIMIT: usize = 5_000;
+pub(super) const MIN_LIMIT: usize = 100;
+pub(super) const MAX_LIMIT: usize = 100_000;
+// px per line before anything has been measured
+const ESTIMATE: f64 = 24.0;
+// px mounted above and below the viewport so fast scrolling does not flash
+const OVERSCAN: f64 = 800.0;
+
+pub(super) struct Scrollback {
+    lines: VecDeque<TerminalLine>,
+    // height of each line in px, None until it has been on screen
+    heights: VecDeque<Option<f64>>,
+    // sum and count of the measured heights
+    measured: (f64, usize),
+    // the same, summed up to any line
+    sums: Sums,
+    limit: usize,
+}
+
+// where the view should stay while new lines are being measured
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) enum Anchor {
+    Bottom,
+    // this line at the top
+    Line(usize),
+}
+
+// the lines to mount and the space standing in for the ones around them
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub(super) struct Window {
+    pub(super) start: usize,
+    pub(super) end: usize,
+    pub(super) before: f64,
+    pub(super) after: f64,
+}
+
+impl Scrollback {
+    // empty, with the limit saved by `scrollback N`
+    pub(super) fn load() -> Self {
+        let limit = storage()
+            .and_then(|s| s.get_item(STORAGE_KEY).ok().flatten())
+            .and_then(|raw| raw.parse().ok())
+            .filter(|limit| (MIN_LIMIT..=MAX_LIMIT).contains(limit))
+            .unwrap_or(DEFAULT_LIMIT);
+        Self::new(limit)
+    }
+
+    fn new(limit: usize) -> Self {
+        Self { lines: VecDeque::new(), heights: VecDeque::new(), measured: (0.0, 0), sums: Sums::new(limit), limit }
+    }
+
+    pub(super) fn lines(&self) -> &VecDeque<TerminalLine> {
+        &self.lines
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub(super) fn limit(&self) -> usize {
+        self.limit
+    }
+
+    // callers check the range, like `scrollback N` does
+    pub(super) fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        if let Some(storage) = storage() {
+            let _ = storage.set_item(STORAGE_KEY, &limit.to_string());
+        }
+        self.trim();
+        self.sums = Sums::new(limit);
+        for (index, height) in self.heights.iter().enumerate() {
+            if let Some(height) = height {
+                self.sums.add(index, *height, 1);
+            }
+        }
+    }
+
+    pub(super) fn push(&mut self, line: TerminalLine) {
+        self.lines.push_back(line);
+        self.heights.push_back(None);
+        self.trim();
+    }
+
+    // trimmed as it goes, so the lines never outgrow the prefix sums
+    pub(super) fn extend(&mut self, lines: impl IntoIterator<Item = TerminalLine>) {
+        lines.into_iter().for_each(|line| self.push(line));
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.lines.clear();
+        self.heights.clear();
+        self.measured = (0.0, 0);
+        self.sums = Sums::new(self.limit);
+    }
+
+    fn trim(&mut self) {
+        while self.lines.len() > self.limit {
+            self.lines.pop_front();
+            if let Some(Some(height)) = self.heights.pop_front() {
+                self.measured.0 -= height;
+                self.measured.1 -= 1;
+                self.sums.add(0, -height, -1);
+            }
+            self.sums.pop_front();
+        }
+    }
+
+    // record the height line `index` was drawn at - true if it changed, so
+    // the caller knows the spacers need resizing
+    pub(super) fn set_height(&mut self, index: usize, height: f64) -> bool {
+        let Some(slot) = self.heights.get_mut(index) else {
+            return false;
+        };
+        match *slot {
+            Some(old) if (old - height).abs() < 0.5 => return false,
+            Some(old) => {
+                self.measured.0 -= old;
+                self.sums.add(index, height - old, 0);
+            }
+            None => {
+                self.measured.1 += 1;
+                self.sums.add(index, height, 1);
+            }
+        }
+        self.measured.0 += height;
+        *slot = Some(height);
+        true
+    }
+
+    // after the width changes every line may wrap differently
+    pub(super) fn forget_heights(&mut self) {
+        self.heights.iter_mut().for_each(|height| *height = None);
+        self.measured = (0.0, 0);
+        self.sums = Sums::new(self.limit);
+    }
+
+    fn estimate(&self) -> f64 {
+        match self.measured {
+            (_, 0) => ESTIMATE,
+            (sum, count) => sum / count as f64,
+        }
+    }
+
+    fn total(&self) -> f64 {
+        self.measured.0 + (self.len() - self.measured.1) as f64 * self.estimate()
+    }
+
+    // px from the top of the output to the top of line `index`
+    pub(super) fn offset(&self, index: usize) -> f64 {
+        let index = index.min(self.len());
+        let (sum, count) = self.sums.prefix(index);
+        sum + (index - count) as f64 * self.estimate()
+    }
+
+    // lines overlapping the viewport scrolled to `scroll_top`, with the
+    // overscan margin on both sides - the first line ending below the top
+    // and the first starting below the bottom, found by binary search
+    pub(super) fn window(&self, scroll_top: f64, viewport: f64) -> Window {
+        let (top, bottom) = (scroll_top - OVERSCAN, scroll_top + viewport + OVERSCAN);
+        let end = partition_point(self.len(), |i| self.offset(i) < bottom);
+        let start = partition_point(end, |i| self.offset(i + 1) <= top);
+        Window { start, end, before: self.offset(start), after: (self.total() - self.offset(end)).max(0.0) }
+    }
+
+    // index of the newest echoed command line
+    pub(super) fn last_command(&self) -> Option<usize> {
+        self.lines.iter().rposition(|line| line.is_command)
+    }
+}
+
+// how many of 0..len pass `pred`, which holds for a prefix of them
+fn partition_point(len: usize, pred: impl Fn(usize) -> bool) -> usize {
+    let (mut lo, mut hi) = (0, len);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+// running (height, count) of the measured lines, as a Fenwick tree over a
+// ring of `limit` slots - lines fall off the front by moving where line 0 is
+struct Sums {
+    tree: Vec<(f64, isize)>,
+    // slot of line 0
+    head: usize,
+}
+
+impl Sums {
+    fn new(limit: usize) -> Self {
+        Self { tree: vec![(0.0, 0); limit.max(1) + 1], head: 0 }
+    }
+
+    fn slots(&self) -> usize {
+        self.tree.len() - 1
+    }
+
+    // the oldest line is gone - callers zero its slot first
+    fn pop_front(&mut self) {
+        self.head = (self.head + 1) % self.slots();
+    }
+
+    fn add(&mut self, index: usize, height: f64, count: isize) {
+        let mut i = (self.head + index) % self.slots() + 1;
+        while i < self.tree.len() {
+            self.tree[i].0 += height;
+            self.tree[i].1 += count;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    // totals over slots 0..slot
+    fn upto(&self, slot: usize) -> (f64, isize) {
+        let (mut sum, mut count) = (0.0, 0);
+        let mut i = slot;
+        while i > 0 {
+            sum += self.tree[i].0;
+            count += self.tree[i].1;
+            i -= i & i.wrapping_neg();
+        }
+        (sum, count)
+    }
+
+    // totals over lines 0..index, which may wrap past the last slot
+    fn prefix(&self, index: usize) -> (f64, usize) {
+        let end = self.head + index;
+        let before = self.upto(self.head);
+        let (sum, count) = if end <= self.slots() {
+            let upto = self.upto(end);
+            (upto.0 - before.0, upto.1 - before.1)
+        } else {
+            let (all, wrapped) = (self.upto(self.slots()), self.upto(end - self.slots()));
+            (all.0 - before.0 + wrapped.0, all.1 - before.1 + wrapped.1)
+        };
+        (sum, count.max(0) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    fn line(text: &str) -> TerminalLine {
+        TerminalLine::text("", text, false)
+    }
+
+    fn filled(lines: usize, limit: usize) -> Scrollback {
+        let mut scrollback = Scrollback::new(limit);
+        scrollback.extend((0..lines).map(|i| line(&format!("line {}", i))));
+        scrollback
+    }
+
+    // the line-by-line walk the prefix sums replace
+    fn naive_offset(scrollback: &Scrollback, index: usize) -> f64 {
+        (0..index).map(|i| scrollback.heights[i].unwrap_or_else(|| scrollback.estimate())).sum()
+    }
+
+    fn assert_offsets(scrollback: &Scrollback) {
+        for index in 0..=scrollback.len() {
+            let (fast, naive) = (scrollback.offset(index), naive_offset(scrollback, index));
+            assert!((fast - naive).abs() < 1e-6, "offset({}) is {}, walking gives {}", index, fast, naive);
+        }
+    }
+
+    #[test]
+    fn offsets_match_a_walk_after_trimming() {
+        // a small limit so the ring wraps several times
+        let mut scrollback = filled(MIN_LIMIT * 3 + 17, MIN_LIMIT);
+        for i in (0..MIN_LIMIT).step_by(3) {
+            scrollback.set_height(i, 20.0 + (i % 7) as f64 * 6.0);
+        }
+        assert_offsets(&scrollback);
+
+        // measured lines fall off the front, new ones arrive unmeasured
+        scrollback.extend((0..MIN_LIMIT / 2).map(|_| line("more")));
+        assert_offsets(&scrollback);
+        scrollback.set_height(MIN_LIMIT - 1, 72.0);
+        scrollback.set_height(0, 48.0);
+        scrollback.set_height(0, 30.0);
+        assert_offsets(&scrollback);
+
+        scrollback.forget_heights();
+        assert_offsets(&scrollback);
+    }
+
+    // only the lines near the viewport are mounted, and the spacers make up
+    // the rest of the height, however long the scrollback is
+    #[test]
+    fn window_is_bounded_at_50k_lines() {
+        let mut scrollback = filled(50_000, MAX_LIMIT);
+        for i in (49_000..50_000).step_by(2) {
+            scrollback.set_height(i, 48.0);
+        }
+        let viewport = 600.0;
+        for scroll_top in [0.0, 123_456.0, scrollback.total() - viewport] {
+            let window = scrollback.window(scroll_top, viewport);
+            assert!(window.end - window.start < 200, "mounts {} lines at {}", window.end - window.start, scroll_top);
+            assert_eq!(window.before, scrollback.offset(window.start));
+            let mounted = scrollback.offset(window.end) - scrollback.offset(window.start);
+            assert!((window.before + mounted + window.after - scrollback.total()).abs() < 1e-6);
+            assert!(window.before <= scroll_top - OVERSCAN || window.start == 0);
+        }
+        assert_eq!(scrollback.window(scrollback.total() - viewport, viewport).end, scrollback.len());
+    }
+
+    #[test]
+    fn trim_enforces_limit() {
+        let mut scrollback = filled(50_000, MIN_LIMIT);
+        assert_eq!(scrollback.len(), MIN_LIMIT);
+        assert_eq!(scrollback.heights.len(), MIN_LIMIT);
+        assert_eq!(scrollback.lines().front().map(|line| line.plain_text()), Some("line 49900".to_string()));
+        assert_eq!(scrollback.lines().back().map(|line| line.plain_text()), Some("line 49999".to_string()));
+
+        // measured lines falling off the front take their height with them
+        scrollback.set_height(0, 48.0);
+        scrollback.set_height(1, 24.0);
+        scrollback.push(line("newest"));
+        assert_eq!(scrollback.len(), MIN_LIMIT);
+        assert_eq!(scrollback.measured, (24.0, 1));
+        assert_eq!(scrollback.lines().back().map(|line| line.plain_text()), Some("newest".to_string()));
+    }
+
+    // what pressing Enter costs the scrollback: echo the command, add its
+    // output, then find the lines to mount at the bottom and the command's
+    // offset to scroll to. wall-clock, so run it on its own:
+    // cargo test --release -- --ignored input_latency
+    #[test]
+    #[ignore]
+    fn input_latency_stays_flat() {
+        let enter = |scrollback: &mut Scrollback| {
+            scrollback.push(TerminalLine::command("projects"));
+            scrollback.extend((0..40).map(|_| line("output")));
+            let window = scrollback.window(scrollback.total() - 600.0, 600.0);
+            for index in window.start..window.end {
+                scrollback.set_height(index, 24.0);
+            }
+            scrollback.last_command().map(|index| scrollback.offset(index))
+        };
+        let latency = |lines: usize| {
+            let mut scrollback = filled(lines, MAX_LIMIT);
+            let start = Instant::now();
+            for _ in 0..1_000 {
+                enter(&mut scrollback);
+            }
+            start.elapsed() / 1_000
+        };
+        let (small, large) = (latency(1_000), latency(50_000));
+        assert!(large <= small * 3 + Duration::from_micros(20), "1k lines: {:?} per Enter, 50k lines: {:?}", small, large);
+    }
+}
@@ -0,0 +1,284 @@
+// aligned columns for command output - cells are measured in display
+// columns, so wide glyphs (🦀, CJK) and styled parts line up like they
+// would in a real terminal instead of by hand-counted spaces
+
+use super::{ansi, OutputPart, TerminalLine};
+
+// code points that take no column - combining marks, zero-width spaces and
+// joiners, variation selectors, emoji skin tone modifiers
+const ZERO_WIDTH: [(u32, u32); 12] = [
+    (0x0300, 0x036F), (0x0483, 0x0489), (0x0591, 0x05BD), (0x0610, 0x061A),
+    (0x064B, 0x065F), (0x200B, 0x200F), (0x2060, 0x2064), (0x20D0, 0x20FF),
+    (0xFE00, 0xFE0F), (0xFE20, 0xFE2F), (0x1F3FB, 0x1F3FF), (0xE0100, 0xE01EF),
+];
+
+// code points that take two columns - East Asian wide/fullwidth ranges and
+// emoji shown in emoji presentation by default
+const WIDE: [(u32, u32); 47] = [
+    (0x1100, 0x115F), (0x231A, 0x231B), (0x2329, 0x232A), (0x23E9, 0x23EC), (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3), (0x25FD, 0x25FE), (0x2614, 0x2615), (0x2648, 0x2653), (0x267F, 0x267F),
+    (0x2693, 0x2693), (0x26A1, 0x26A1), (0x26AA, 0x26AB), (0x26BD, 0x26BE), (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE), (0x26D4, 0x26D4), (0x26EA, 0x26EA), (0x26F2, 0x26F5), (0x26FA, 0x26FD),
+    (0x2705, 0x2705), (0x270A, 0x270B), (0x2728, 0x2728), (0x274C, 0x274E), (0x2753, 0x2757),
+    (0x2795, 0x2797), (0x27B0, 0x27BF), (0x2B1B, 0x2B1C), (0x2B50, 0x2B55), (0x2E80, 0x303E),
+    (0x3041, 0x33FF), (0x3400, 0x4DBF), (0x4E00, 0x9FFF), (0xA000, 0xA4CF), (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF), (0xFE30, 0xFE4F), (0xFF00, 0xFF60), (0xFFE0, 0xFFE6), (0x1F004, 0x1F0CF),
+    (0x1F18E, 0x1F19A), (0x1F200, 0x1F251), (0x1F300, 0x1F64F), (0x1F680, 0x1F6FF), (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1FAFF), (0x20000, 0x3FFFD),
+];
+
+pub(super) fn char_width(c: char) -> usize {
+    let in_ranges = |ranges: &[(u32, u32)]| ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(&(c as u32)));
+    if c.is_control() || in_ranges(&ZERO_WIDTH) {
+        0
+    } else if in_ranges(&WIDE) {
+        2
+    } else {
+        1
+    }
+}
+
+// columns `text` takes on screen. ZWJ emoji sequences are counted per
+// component, which overstates them - none are used in the content
+pub(super) fn width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) enum Border {
+    // columns separated by spaces only, headers in bold
+    None,
+    Single,
+    Rounded,
+    Double,
+    Heavy,
+}
+
+// box-drawing pieces of one border style
+struct Frame {
+    horizontal: char,
+    vertical: char,
+    // [left, middle, right] of the top, separator and bottom rules
+    top: [char; 3],
+    middle: [char; 3],
+    bottom: [char; 3],
+}
+
+impl Border {
+    // styles that can be picked by name, e.g. `column -b rounded`
+    pub(super) const NAMED: [(&'static str, Border); 4] = [
+        ("single", Border::Single),
+        ("rounded", Border::Rounded),
+        ("double", Border::Double),
+        ("heavy", Border::Heavy),
+    ];
+
+    pub(super) fn from_name(name: &str) -> Option<Self> {
+        Self::NAMED.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|&(_, border)| border)
+    }
+
+    fn frame(self) -> Option<Frame> {
+        let frame = |horizontal, vertical, top, middle, bottom| Some(Frame { horizontal, vertical, top, middle, bottom });
+        match self {
+            Border::None => None,
+            Border::Single => frame('─', '│', ['┌', '┬', '┐'], ['├', '┼', '┤'], ['└', '┴', '┘']),
+            Border::Rounded => frame('─', '│', ['╭', '┬', '╮'], ['├', '┼', '┤'], ['╰', '┴', '╯']),
+            Border::Double => frame('═', '║', ['╔', '╦', '╗'], ['╠', '╬', '╣'], ['╚', '╩', '╝']),
+            Border::Heavy => frame('━', '┃', ['┏', '┳', '┓'], ['┣', '╋', '┫'], ['┗', '┻', '┛']),
+        }
+    }
+}
+
+// one table cell - styled parts, measured by the text they show
+#[derive(Clone, PartialEq, Debug, Default)]
+pub(super) struct Cell(Vec<OutputPart>);
+
+impl Cell {
+    pub(super) fn bold(text: &str) -> Self {
+        Cell(vec![OutputPart::Bold(text.to_string())])
+    }
+
+    // a command name, styled like the ones in `help`
+    pub(super) fn cmd(text: &str) -> Self {
+        Cell(vec![OutputPart::CmdName(text.to_string())])
+    }
+
+    pub(super) fn link(text: &str, url: &str) -> Self {
+        Cell(vec![OutputPart::Link { text: text.to_string(), url: url.to_string() }])
+    }
+
+    pub(super) fn parts(parts: Vec<OutputPart>) -> Self {
+        Cell(parts)
+    }
+
+    fn width(&self) -> usize {
+        self.0.iter().map(|part| width(part.text())).sum()
+    }
+}
+
+// plain cells go through the ANSI parser, like `TerminalLine::text`
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        Cell(ansi::parse(text))
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Cell::from(text.as_str())
+    }
+}
+
+// rows of cells rendered as aligned `TerminalLine`s
+// Table::new().headers(..).align(1, Align::Right).row(..).render()
+pub(super) struct Table {
+    headers: Option<Vec<Cell>>,
+    rows: Vec<Vec<Cell>>,
+    align: Vec<Align>,
+    min_width: Vec<usize>,
+    border: Border,
+    indent: String,
+    gap: usize,
+}
+
+impl Table {
+    // borderless, two-space indent and gap - the terminal's usual layout
+    pub(super) fn new() -> Self {
+        Self { headers: None, rows: vec![], align: vec![], min_width: vec![], border: Border::None, indent: "  ".into(), gap: 2 }
+    }
+
+    pub(super) fn headers(mut self, headers: &[&str]) -> Self {
+        self.headers = Some(headers.iter().map(|h| Cell::bold(h)).collect());
+        self
+    }
+
+    // columns are left-aligned unless set here
+    pub(super) fn align(mut self, column: usize, align: Align) -> Self {
+        if self.align.len() <= column {
+            self.align.resize(column + 1, Align::Left);
+        }
+        self.align[column] = align;
+        self
+    }
+
+    // keep a column at least this wide, e.g. to line up several tables
+    pub(super) fn min_width(mut self, column: usize, width: usize) -> Self {
+        if self.min_width.len() <= column {
+            self.min_width.resize(column + 1, 0);
+        }
+        self.min_width[column] = width;
+        self
+    }
+
+    pub(super) fn border(mut self, border: Border) -> Self {
+        self.border = border;
+        self
+    }
+
+    pub(super) fn indent(mut self, indent: &str) -> Self {
+        self.indent = indent.to_string();
+        self
+    }
+
+    // spaces between columns when there is no border
+    pub(super) fn gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub(super) fn row(mut self, cells: Vec<Cell>) -> Self {
+        self.push(cells);
+        self
+    }
+
+    // `row` for tables filled in a loop
+    pub(super) fn push(&mut self, cells: Vec<Cell>) {
+        self.rows.push(cells);
+    }
+
+    pub(super) fn render(self) -> Vec<TerminalLine> {
+        let columns = self.headers.iter().chain(&self.rows).map(Vec::len).max().unwrap_or(0);
+        let mut widths = vec![0; columns];
+        for row in self.headers.iter().chain(&self.rows) {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(cell.width());
+            }
+        }
+        for (width, min) in widths.iter_mut().zip(&self.min_width) {
+            *width = (*width).max(*min);
+        }
+
+        let frame = self.border.frame();
+        let rule = |[left, middle, right]: [char; 3], horizontal: char| {
+            let segments: Vec<String> = widths.iter().map(|w| horizontal.to_string().repeat(w + 2)).collect();
+            let line = format!("{}{}{}{}", self.indent, left, segments.join(&middle.to_string()), right);
+            TerminalLine::text("", &line, false)
+        };
+
+        let mut lines = vec![];
+        if let Some(frame) = &frame {
+            lines.push(rule(frame.top, frame.horizontal));
+        }
+        if let Some(headers) = &self.headers {
+            lines.push(self.render_row(headers, &widths, frame.as_ref()));
+            if let Some(frame) = &frame {
+                lines.push(rule(frame.middle, frame.horizontal));
+            }
+        }
+        lines.extend(self.rows.iter().map(|row| self.render_row(row, &widths, frame.as_ref())));
+        if let Some(frame) = &frame {
+            lines.push(rule(frame.bottom, frame.horizontal));
+        }
+        lines
+    }
+
+    fn render_row(&self, row: &[Cell], widths: &[usize], frame: Option<&Frame>) -> TerminalLine {
+        let mut parts = vec![OutputPart::Text(self.indent.clone())];
+        let empty = Cell::default();
+        let last = widths.len().saturating_sub(1);
+        for (i, width) in widths.iter().enumerate() {
+            let cell = row.get(i).unwrap_or(&empty);
+            let slack = width.saturating_sub(cell.width());
+            let (before, mut after) = match self.align.get(i).copied().unwrap_or(Align::Left) {
+                Align::Left => (0, slack),
+                Align::Right => (slack, 0),
+                Align::Center => (slack / 2, slack - slack / 2),
+            };
+            match frame {
+                Some(frame) => {
+                    push_text(&mut parts, format!("{} {}", frame.vertical, " ".repeat(before)));
+                    after += 1;
+                }
+                None if i > 0 => push_text(&mut parts, " ".repeat(self.gap + before)),
+                None => push_text(&mut parts, " ".repeat(before)),
+            }
+            parts.extend(cell.0.iter().cloned());
+            // no trailing spaces after the last borderless column
+            if frame.is_some() || i < last {
+                push_text(&mut parts, " ".repeat(after));
+            }
+        }
+        if let Some(frame) = frame {
+            push_text(&mut parts, frame.vertical.to_string());
+        }
+        TerminalLine::parts("", parts)
+    }
+}
+
+// padding joins the text part before it instead of adding a new one
+fn push_text(parts: &mut Vec<OutputPart>, text: String) {
+    if text.is_empty() {
+        return;
+    }
+    match parts.last_mut() {
+        Some(OutputPart::Text(last)) => last.push_str(&text),
+        _ => parts.push(OutputPart::Text(text)),
+    }
+}
@@ -0,0 +1,104 @@
+// timed steps that can be stopped or hurried along - the boot animation.
+// every pending timeout is kept so nothing fires after `cancel` (e.g. boot
+// lines landing after a `clear`), and `finish` runs what is left at once
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use leptos::leptos_dom::helpers::TimeoutHandle;
+use leptos::*;
+
+pub(super) type Step = Box<dyn FnOnce()>;
+
+#[derive(Default)]
+struct Schedule {
+    // steps not yet run, in the order they fire
+    steps: VecDeque<Step>,
+    // runs once the steps are done, however they got there
+    then: Option<Step>,
+    timers: Vec<TimeoutHandle>,
+}
+
+// handle to the schedule, cheap to copy into event handlers and `Context`
+#[derive(Clone, Copy)]
+pub(super) struct Timeline(StoredValue<Schedule>);
+
+impl Timeline {
+    pub(super) fn new() -> Self {
+        Self(store_value(Schedule::default()))
+    }
+
+    pub(super) fn is_playing(self) -> bool {
+        self.0.with_value(|s| s.then.is_some())
+    }
+
+    // replace whatever is playing with `steps` (ms from now, ascending) and
+    // `then` after the last one. with reduced motion everything runs now
+    pub(super) fn play(self, steps: Vec<(u64, Step)>, then: Step) {
+        self.cancel();
+        let mut delays: Vec<u64> = steps.iter().map(|(delay, _)| *delay).collect();
+        // one more timer for `then`, right after the last step
+        delays.push(delays.last().copied().unwrap_or(0));
+        self.0.update_value(|s| {
+            s.steps = steps.into_iter().map(|(_, step)| step).collect();
+            s.then = Some(then);
+        });
+        if reduced_motion() {
+            self.finish();
+            return;
+        }
+        // timeouts with the same delay fire in the order they were set, so
+        // each one just runs the next step (or `then`)
+        let timers = delays
+            .into_iter()
+            .filter_map(|delay| set_timeout_with_handle(move || { self.advance(); }, Duration::from_millis(delay)).ok())
+            .collect();
+        self.0.update_value(|s| s.timers = timers);
+    }
+
+    // run every remaining step now, e.g. when a key is pressed mid-boot
+    pub(super) fn finish(self) {
+        while self.advance() {}
+    }
+
+    // drop the remaining steps - `then` still runs so the caller is never
+    // left waiting for an end that would not come
+    pub(super) fn cancel(self) {
+        let then = self.0.try_update_value(|s| {
+            s.timers.drain(..).for_each(|timer| timer.clear());
+            s.steps.clear();
+            s.then.take()
+        });
+        if let Some(then) = then.flatten() {
+            then();
+        }
+    }
+
+    // run the next step - outside the borrow, so steps may touch signals
+    // freely. false once there is nothing left
+    fn advance(self) -> bool {
+        let next = self.0.try_update_value(|s| match s.steps.pop_front() {
+            Some(step) => Some(step),
+            None => {
+                s.timers.drain(..).for_each(|timer| timer.clear());
+                s.then.take()
+            }
+        });
+        match next.flatten() {
+            Some(step) => {
+                step();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// the visitor asked the OS for less animation
+fn reduced_motion() -> bool {
+    window()
+        .match_media("(prefers-reduced-motion: reduce)")
+        .ok()
+        .flatten()
+        .is_some_and(|query| query.matches())
+}
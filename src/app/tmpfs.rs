@@ -0,0 +1,429 @@
+// the writable part of the filesystem - files and directories the visitor
+// creates under /tmp and ~, mounted over the read-only content tree and kept
+// in localStorage. everything else fails with EACCES, like a real box would.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use super::commands::{Category, Command, Context, Output};
+use super::content::portfolio;
+use super::portfolio::Portfolio;
+use super::tokenizer::Args;
+use super::vfs::{self, join, lookup, resolve, tree, File, Node, HOME};
+use super::{storage, TerminalLine};
+
+const STORAGE_KEY: &str = "vanguard.fs";
+// bytes of paths plus contents - localStorage is small and shared with history
+const QUOTA: usize = 64 * 1024;
+const TMP: &str = "/tmp";
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+enum Entry {
+    Dir,
+    File(String),
+}
+
+// errno-style failures, worded like coreutils
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) enum FsError {
+    NotFound,
+    Exists,
+    NotDir,
+    IsDir,
+    Access,
+    NoSpace,
+    Invalid,
+}
+
+impl fmt::Display for FsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            FsError::NotFound => "No such file or directory",
+            FsError::Exists => "File exists",
+            FsError::NotDir => "Not a directory",
+            FsError::IsDir => "Is a directory",
+            FsError::Access => "Permission denied",
+            FsError::NoSpace => "No space left on device",
+            FsError::Invalid => "Invalid argument",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+thread_local! {
+    // canonical absolute path -> entry, parents sort before their children
+    static OVERLAY: RefCell<BTreeMap<String, Entry>> = RefCell::new(load());
+}
+
+fn load() -> BTreeMap<String, Entry> {
+    storage()
+        .and_then(|s| s.get_item(STORAGE_KEY).ok().flatten())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn size(entries: &BTreeMap<String, Entry>) -> usize {
+    entries
+        .iter()
+        .map(|(path, entry)| path.len() + if let Entry::File(text) = entry { text.len() } else { 0 })
+        .sum()
+}
+
+// swap in the changed overlay - all or nothing, so a write that would
+// go over the quota leaves everything as it was
+fn commit(entries: BTreeMap<String, Entry>) -> Result<(), FsError> {
+    if size(&entries) > QUOTA {
+        return Err(FsError::NoSpace);
+    }
+    if let Some(storage) = storage() {
+        let json = serde_json::to_string(&entries).expect("overlay serializes");
+        storage.set_item(STORAGE_KEY, &json).map_err(|_| FsError::NoSpace)?;
+    }
+    OVERLAY.with(|o| *o.borrow_mut() = entries);
+    Ok(())
+}
+
+fn entries() -> BTreeMap<String, Entry> {
+    OVERLAY.with(|o| o.borrow().clone())
+}
+
+// add /tmp and the visitor's files to the content tree
+pub(super) fn mount(root: &mut Node) {
+    root.insert(TMP, Node::Dir(vec![]));
+    OVERLAY.with(|o| {
+        for (path, entry) in o.borrow().iter() {
+            let node = match entry {
+                Entry::Dir => Node::Dir(vec![]),
+                Entry::File(text) => Node::File(File::Text(text.clone())),
+            };
+            root.insert(path, node);
+        }
+    });
+}
+
+// whether `abs` lies in the overlay - anything below /tmp, or below ~ but
+// outside the content files and directories
+fn writable(abs: &str) -> bool {
+    if abs.strip_prefix(TMP).is_some_and(|rest| rest.starts_with('/')) {
+        return true;
+    }
+    let Some(rest) = abs.strip_prefix(HOME).and_then(|rest| rest.strip_prefix('/')) else {
+        return false;
+    };
+    let first = rest.split('/').next().unwrap_or_default();
+    lookup(&vfs::root(&portfolio()), &format!("{}/{}", HOME, first)).is_none()
+}
+
+// the canonical path `abs` is (or would be) stored under, checking the
+// parent directory exists and the place is writable
+fn place(root: &Node, abs: &str) -> Result<String, FsError> {
+    let path = match lookup(root, abs) {
+        Some((canonical, _)) => canonical,
+        None => {
+            let (parent, name) = abs.rsplit_once('/').unwrap_or(("", abs));
+            match lookup(root, if parent.is_empty() { "/" } else { parent }) {
+                Some((parent, node)) if node.is_dir() => join(&parent, name),
+                Some(_) => return Err(FsError::NotDir),
+                None => return Err(FsError::NotFound),
+            }
+        }
+    };
+    if writable(&path) {
+        Ok(path)
+    } else {
+        Err(FsError::Access)
+    }
+}
+
+// create or overwrite (or append to) a file - the `>` and `>>` redirects
+pub(super) fn write(abs: &str, text: &str, append: bool) -> Result<(), FsError> {
+    let root = tree(&portfolio());
+    if lookup(&root, abs).is_some_and(|(_, node)| node.is_dir()) {
+        return Err(FsError::IsDir);
+    }
+    let path = place(&root, abs)?;
+    let mut entries = entries();
+    let old = match entries.get(&path) {
+        Some(Entry::File(old)) if append => old.clone(),
+        _ => String::new(),
+    };
+    entries.insert(path, Entry::File(old + text));
+    commit(entries)
+}
+
+fn touch(abs: &str) -> Result<(), FsError> {
+    let root = tree(&portfolio());
+    let path = place(&root, abs)?;
+    if lookup(&root, &path).is_some() {
+        return Ok(());
+    }
+    let mut entries = entries();
+    entries.insert(path, Entry::File(String::new()));
+    commit(entries)
+}
+
+fn mkdir(abs: &str, parents: bool) -> Result<(), FsError> {
+    let root = tree(&portfolio());
+    let mut entries = entries();
+    // with -p every missing directory on the way is made, outermost first
+    let mut missing = vec![abs.to_string()];
+    if parents {
+        while let Some((parent, _)) = missing.last().and_then(|p| p.rsplit_once('/')) {
+            if parent.is_empty() || lookup(&root, parent).is_some() {
+                break;
+            }
+            missing.push(parent.to_string());
+        }
+    }
+    for dir in missing.iter().rev() {
+        if let Some((_, node)) = lookup(&root, dir) {
+            return match node.is_dir() && parents {
+                true => Ok(()),
+                false => Err(FsError::Exists),
+            };
+        }
+        // parents made earlier in this loop are only in `entries` so far
+        let made = dir.rsplit_once('/').and_then(|(parent, name)| {
+            entries.keys().find(|p| p.eq_ignore_ascii_case(parent)).map(|parent| join(parent, name))
+        });
+        let path = match made {
+            Some(path) => path,
+            None => place(&root, dir)?,
+        };
+        entries.insert(path, Entry::Dir);
+    }
+    commit(entries)
+}
+
+fn remove(abs: &str, recursive: bool) -> Result<(), FsError> {
+    let root = tree(&portfolio());
+    let (path, node) = lookup(&root, abs).ok_or(FsError::NotFound)?;
+    if !writable(&path) {
+        return Err(FsError::Access);
+    }
+    if node.is_dir() && !recursive {
+        return Err(FsError::IsDir);
+    }
+    let mut entries = entries();
+    let inside = format!("{}/", path);
+    entries.retain(|p, _| *p != path && !p.starts_with(&inside));
+    commit(entries)
+}
+
+// copy `src` to `dst` (into it, when `dst` is a directory). content files
+// are copied as plain text, so `cp about.md /tmp` gives an editable copy
+fn copy(src: &str, dst: &str, recursive: bool, entries: &mut BTreeMap<String, Entry>) -> Result<String, FsError> {
+    let portfolio = portfolio();
+    let root = tree(&portfolio);
+    let (from, node) = lookup(&root, src).ok_or(FsError::NotFound)?;
+    if node.is_dir() && !recursive {
+        return Err(FsError::IsDir);
+    }
+    let dst = match lookup(&root, dst) {
+        Some((dir, target)) if target.is_dir() => join(&dir, from.rsplit('/').next().unwrap_or_default()),
+        _ => dst.to_string(),
+    };
+    if lookup(&root, &dst).is_some_and(|(_, target)| target.is_dir() != node.is_dir()) {
+        return Err(if node.is_dir() { FsError::NotDir } else { FsError::IsDir });
+    }
+    let to = place(&root, &dst)?;
+    if to == from || to.starts_with(&format!("{}/", from)) {
+        return Err(FsError::Invalid);
+    }
+    copy_node(node, &to, &portfolio, entries);
+    Ok(from)
+}
+
+fn copy_node(node: &Node, to: &str, portfolio: &Portfolio, entries: &mut BTreeMap<String, Entry>) {
+    match node {
+        Node::Dir(_) => {
+            entries.insert(to.to_string(), Entry::Dir);
+            for (name, child) in node.entries(true) {
+                copy_node(child, &join(to, name), portfolio, entries);
+            }
+        }
+        // unreadable files such as .env are skipped
+        Node::File(file) => {
+            if let Ok(text) = vfs::text(file, portfolio) {
+                entries.insert(to.to_string(), Entry::File(text));
+            }
+        }
+    }
+}
+
+fn copy_or_move(src: &str, dst: &str, recursive: bool, moving: bool) -> Result<(), FsError> {
+    let mut entries = entries();
+    if moving {
+        let (path, _) = lookup(&tree(&portfolio()), src).ok_or(FsError::NotFound)?;
+        if !writable(&path) {
+            return Err(FsError::Access);
+        }
+    }
+    let from = copy(src, dst, recursive, &mut entries)?;
+    if moving {
+        let inside = format!("{}/", from);
+        entries.retain(|p, _| *p != from && !p.starts_with(&inside));
+    }
+    commit(entries)
+}
+
+// commands
+
+// `cmd: cannot <verb> 'path': reason` lines and the status to go with them
+fn report(results: Vec<(String, Result<(), FsError>)>, what: &str) -> Output {
+    let mut lines = vec![];
+    for (path, result) in results {
+        if let Err(err) = result {
+            lines.push(TerminalLine::text("", &format!("  {} '{}': {}", what, path, err), false));
+        }
+    }
+    let status = if lines.is_empty() { 0 } else { 1 };
+    Output::new(status, lines)
+}
+
+pub(super) struct Touch;
+
+impl Command for Touch {
+    fn name(&self) -> &'static str { "touch" }
+    fn summary(&self) -> &'static str { "Create empty files" }
+    fn usage(&self) -> &'static str { "touch <file...>" }
+    fn description(&self) -> &'static str {
+        "Creates each FILE if it does not exist. Files can be made in ~ and /tmp, the portfolio content itself is read-only."
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["touch notes.txt", "touch /tmp/a /tmp/b"] }
+    fn takes_paths(&self) -> bool { true }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        if argv.len() < 2 {
+            return Output::error(2, &format!("usage: {}", self.usage()));
+        }
+        let cwd = ctx.cwd.get_untracked();
+        let results = argv[1..].iter().map(|path| (path.clone(), touch(&resolve(&cwd, path)))).collect();
+        report(results, "touch: cannot touch")
+    }
+}
+
+pub(super) struct Mkdir;
+
+impl Command for Mkdir {
+    fn name(&self) -> &'static str { "mkdir" }
+    fn summary(&self) -> &'static str { "Make directories" }
+    fn usage(&self) -> &'static str { "mkdir [-p] <dir...>" }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-p, --parents", "Make missing parents too, no error if it exists")]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["mkdir notes", "mkdir -p /tmp/a/b/c"] }
+    fn takes_paths(&self) -> bool { true }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &[]);
+        if args.positional.is_empty() {
+            return Output::error(2, &format!("usage: {}", self.usage()));
+        }
+        let parents = args.has(&["p", "parents"]);
+        let cwd = ctx.cwd.get_untracked();
+        let results = args.positional.iter().map(|path| (path.clone(), mkdir(&resolve(&cwd, path), parents))).collect();
+        report(results, "mkdir: cannot create directory")
+    }
+}
+
+pub(super) struct Rm;
+
+impl Command for Rm {
+    fn name(&self) -> &'static str { "rm" }
+    fn summary(&self) -> &'static str { "Remove files" }
+    fn usage(&self) -> &'static str { "rm [-rf] <path...>" }
+    fn description(&self) -> &'static str {
+        "Removes files you created. Directories need -r. The portfolio content is read-only, so `rm -rf /` only gets you a Permission denied."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-r, --recursive", "Remove directories and their contents"),
+            ("-f, --force", "Ignore missing files"),
+        ]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["rm notes.txt", "rm -r /tmp/scratch"] }
+    fn takes_paths(&self) -> bool { true }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &[]);
+        let force = args.has(&["f", "force"]);
+        if args.positional.is_empty() && !force {
+            return Output::error(2, &format!("usage: {}", self.usage()));
+        }
+        let recursive = args.has(&["r", "R", "recursive"]);
+        let cwd = ctx.cwd.get_untracked();
+        let results = args
+            .positional
+            .iter()
+            .map(|path| (path.clone(), remove(&resolve(&cwd, path), recursive)))
+            .filter(|(_, result)| !(force && *result == Err(FsError::NotFound)))
+            .collect();
+        report(results, "rm: cannot remove")
+    }
+}
+
+pub(super) struct Cp;
+
+impl Command for Cp {
+    fn name(&self) -> &'static str { "cp" }
+    fn summary(&self) -> &'static str { "Copy files" }
+    fn usage(&self) -> &'static str { "cp [-r] <source...> <dest>" }
+    fn description(&self) -> &'static str {
+        "Copies SOURCE to DEST, or into DEST when it is a directory. Portfolio files can be copied out as plain text, e.g. to edit with `>>`."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-r, --recursive", "Copy directories and their contents")]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["cp about.md /tmp", "cp -r projects ~/backup"] }
+    fn takes_paths(&self) -> bool { true }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        transfer(self.usage(), argv, ctx, false)
+    }
+}
+
+pub(super) struct Mv;
+
+impl Command for Mv {
+    fn name(&self) -> &'static str { "mv" }
+    fn summary(&self) -> &'static str { "Move or rename files" }
+    fn usage(&self) -> &'static str { "mv <source...> <dest>" }
+    fn description(&self) -> &'static str {
+        "Renames SOURCE to DEST, or moves it into DEST when that is a directory. Only your own files can be moved."
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["mv notes.txt /tmp", "mv draft.md final.md"] }
+    fn takes_paths(&self) -> bool { true }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        transfer(self.usage(), argv, ctx, true)
+    }
+}
+
+// cp and mv - each source is done on its own, so one bad path doesn't
+// stop the rest
+fn transfer(usage: &str, argv: &[String], ctx: &Context, moving: bool) -> Output {
+    let args = Args::parse(argv, &[]);
+    let Some((dst, sources)) = args.positional.split_last().filter(|(_, sources)| !sources.is_empty()) else {
+        return Output::error(2, &format!("usage: {}", usage));
+    };
+    let cwd = ctx.cwd.get_untracked();
+    let dst = resolve(&cwd, dst);
+    if sources.len() > 1 && !lookup(&tree(&portfolio()), &dst).is_some_and(|(_, node)| node.is_dir()) {
+        return Output::error(1, &format!("{}: target '{}' is not a directory", argv[0], args.positional[sources.len()]));
+    }
+    let recursive = moving || args.has(&["r", "R", "recursive"]);
+
+    let results = sources.iter().map(|src| (src.clone(), copy_or_move(&resolve(&cwd, src), &dst, recursive, moving))).collect();
+    let what = if moving { "mv: cannot move" } else { "cp: cannot copy" };
+    report(results, what)
+}
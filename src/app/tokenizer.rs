@@ -0,0 +1,446 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Clone, PartialEq, Debug)]
+pub(super) enum SyntaxError {
+    UnterminatedQuote(char),
+    TrailingBackslash,
+    UnexpectedToken(&'static str),
+    BadSubstitution,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyntaxError::UnterminatedQuote(q) => write!(f, "unterminated {} quote", q),
+            SyntaxError::TrailingBackslash => write!(f, "trailing backslash"),
+            SyntaxError::UnexpectedToken(token) => write!(f, "unexpected token `{}'", token),
+            SyntaxError::BadSubstitution => write!(f, "bad substitution"),
+        }
+    }
+}
+
+// a piece of a word - literal text or a `$NAME` / `$?` reference that is
+// expanded right before the command runs
+#[derive(Clone, PartialEq, Debug)]
+pub(super) enum Segment {
+    Literal(String),
+    Var(String),
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub(super) struct Word(Vec<Segment>);
+
+impl Word {
+    fn push_char(&mut self, c: char) {
+        match self.0.last_mut() {
+            Some(Segment::Literal(text)) => text.push(c),
+            _ => self.0.push(Segment::Literal(c.to_string())),
+        }
+    }
+
+    fn push_var(&mut self, name: String) {
+        self.0.push(Segment::Var(name));
+    }
+
+    // substitute variables, unknown names expand to nothing like in sh
+    pub(super) fn expand(&self, lookup: impl Fn(&str) -> Option<String>) -> String {
+        self.0
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => text.clone(),
+                Segment::Var(name) => lookup(name).unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub(super) enum Token {
+    Word(Word),
+    Pipe,
+    // `;`
+    Semi,
+    // `&&`
+    And,
+    // `||`
+    Or,
+    // `>`, or `>>` when appending
+    Redirect { append: bool },
+}
+
+// read a variable name after `$` - `$?`, `$NAME` or `${NAME}`
+// returns None (and consumes nothing) when `$` is just a dollar sign
+fn read_var(chars: &mut Peekable<Chars>) -> Result<Option<String>, SyntaxError> {
+    match chars.peek() {
+        Some('?') => {
+            chars.next();
+            Ok(Some("?".to_string()))
+        }
+        Some('{') => {
+            chars.next();
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some('}') if !name.is_empty() => return Ok(Some(name)),
+                    Some(c) if c.is_ascii_alphanumeric() || c == '_' || (c == '?' && name.is_empty()) => name.push(c),
+                    _ => return Err(SyntaxError::BadSubstitution),
+                }
+            }
+        }
+        Some(c) if c.is_ascii_alphabetic() || *c == '_' => {
+            let mut name = String::new();
+            while let Some(&c) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                name.push(c);
+                chars.next();
+            }
+            Ok(Some(name))
+        }
+        _ => Ok(None),
+    }
+}
+
+// shell-style word splitting
+// single quotes are literal, double quotes allow \" \\ \$ escapes and `$VAR`,
+// a bare backslash escapes the next char. case is left alone.
+// unquoted `|`, `||`, `&&`, `;`, `>` and `>>` are operators even without surrounding spaces.
+pub(super) fn tokenize(line: &str) -> Result<Vec<Token>, SyntaxError> {
+    let mut tokens = vec![];
+    let mut word = Word::default();
+    // tracks `""` so an empty quoted arg still counts as a word
+    let mut in_word = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() || matches!(c, '|' | '&' | ';' | '>') => {
+                if in_word {
+                    tokens.push(Token::Word(std::mem::take(&mut word)));
+                    in_word = false;
+                }
+                match c {
+                    '|' if chars.next_if_eq(&'|').is_some() => tokens.push(Token::Or),
+                    '|' => tokens.push(Token::Pipe),
+                    '&' if chars.next_if_eq(&'&').is_some() => tokens.push(Token::And),
+                    // no job control here
+                    '&' => return Err(SyntaxError::UnexpectedToken("&")),
+                    ';' => tokens.push(Token::Semi),
+                    '>' => tokens.push(Token::Redirect { append: chars.next_if_eq(&'>').is_some() }),
+                    _ => {}
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push_char(c),
+                        None => return Err(SyntaxError::UnterminatedQuote('\'')),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$')) => word.push_char(c),
+                            Some(c) => {
+                                word.push_char('\\');
+                                word.push_char(c);
+                            }
+                            None => return Err(SyntaxError::UnterminatedQuote('"')),
+                        },
+                        Some('$') => match read_var(&mut chars)? {
+                            Some(name) => word.push_var(name),
+                            None => word.push_char('$'),
+                        },
+                        Some(c) => word.push_char(c),
+                        None => return Err(SyntaxError::UnterminatedQuote('"')),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some(c) => {
+                    in_word = true;
+                    word.push_char(c);
+                }
+                None => return Err(SyntaxError::TrailingBackslash),
+            },
+            '$' => {
+                in_word = true;
+                match read_var(&mut chars)? {
+                    Some(name) => word.push_var(name),
+                    None => word.push_char('$'),
+                }
+            }
+            c => {
+                in_word = true;
+                word.push_char(c);
+            }
+        }
+    }
+    if in_word {
+        tokens.push(Token::Word(word));
+    }
+    Ok(tokens)
+}
+
+// the inverse of `tokenize` for one word - single-quoted when it has
+// whitespace or anything the tokenizer would treat specially
+pub(super) fn quote(word: &str) -> String {
+    let special = |c: char| c.is_whitespace() || matches!(c, '|' | '&' | ';' | '>' | '\'' | '"' | '\\' | '$');
+    if !word.is_empty() && !word.contains(special) {
+        return word.to_string();
+    }
+    format!("'{}'", word.replace('\'', "'\\''"))
+}
+
+// how a pipeline hangs off the one before it
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) enum Connector {
+    // first pipeline, or after `;`
+    Always,
+    // `&&` - only if the last status was 0
+    And,
+    // `||` - only if the last status was non-zero
+    Or,
+}
+
+// `> file` or `>> file` - where the last stage's output goes instead of the screen
+#[derive(Clone, PartialEq, Debug)]
+pub(super) struct Redirect {
+    pub(super) append: bool,
+    pub(super) target: Word,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub(super) struct Pipeline {
+    pub(super) connector: Connector,
+    // one unexpanded argv per stage
+    pub(super) stages: Vec<Vec<Word>>,
+    pub(super) redirect: Option<Redirect>,
+}
+
+impl Pipeline {
+    fn new(connector: Connector) -> Self {
+        Self { connector, stages: vec![], redirect: None }
+    }
+}
+
+fn symbol(token: &Token) -> &'static str {
+    match token {
+        Token::Word(_) => "word",
+        Token::Pipe => "|",
+        Token::Semi => ";",
+        Token::And => "&&",
+        Token::Or => "||",
+        Token::Redirect { append: false } => ">",
+        Token::Redirect { append: true } => ">>",
+    }
+}
+
+// group tokens into `a | b && c; d` style command lists
+// a redirect may sit anywhere in the last stage: `echo hi > f` or `> f echo hi`
+pub(super) fn parse(tokens: Vec<Token>) -> Result<Vec<Pipeline>, SyntaxError> {
+    let mut pipelines = vec![];
+    let mut current = Pipeline::new(Connector::Always);
+    let mut argv = vec![];
+    let mut tokens = tokens.into_iter();
+
+    while let Some(token) = tokens.next() {
+        let (next, symbol) = match token {
+            Token::Word(word) => {
+                argv.push(word);
+                continue;
+            }
+            Token::Redirect { append } => {
+                let target = match tokens.next() {
+                    Some(Token::Word(target)) => target,
+                    Some(other) => return Err(SyntaxError::UnexpectedToken(symbol(&other))),
+                    None => return Err(SyntaxError::UnexpectedToken("newline")),
+                };
+                current.redirect = Some(Redirect { append, target });
+                continue;
+            }
+            Token::Pipe => {
+                // output already goes to a file, there is nothing left to pipe
+                if argv.is_empty() || current.redirect.is_some() {
+                    return Err(SyntaxError::UnexpectedToken("|"));
+                }
+                current.stages.push(std::mem::take(&mut argv));
+                continue;
+            }
+            Token::Semi => (Connector::Always, ";"),
+            Token::And => (Connector::And, "&&"),
+            Token::Or => (Connector::Or, "||"),
+        };
+        // `a ;; b`, `a || ; b` and a leading `&&` have no command before the
+        // separator - only the end of the line may follow a bare `;`
+        if argv.is_empty() {
+            return Err(SyntaxError::UnexpectedToken(symbol));
+        }
+        current.stages.push(std::mem::take(&mut argv));
+        let done = std::mem::replace(&mut current, Pipeline::new(next));
+        pipelines.push(done);
+    }
+
+    if argv.is_empty() {
+        // `a |` or `a &&` left dangling - `a;` and an empty line are fine
+        match (current.stages.is_empty(), current.connector) {
+            _ if current.redirect.is_some() => return Err(SyntaxError::UnexpectedToken("newline")),
+            (false, _) => return Err(SyntaxError::UnexpectedToken("|")),
+            (true, Connector::And) => return Err(SyntaxError::UnexpectedToken("&&")),
+            (true, Connector::Or) => return Err(SyntaxError::UnexpectedToken("||")),
+            (true, Connector::Always) => {}
+        }
+    } else {
+        current.stages.push(argv);
+        pipelines.push(current);
+    }
+    Ok(pipelines)
+}
+
+// flags and positional args pulled out of an argv
+// `--name`, `--name=value`, `-abc` clusters and `--` are understood.
+// flags listed in `takes_value` consume a value (`-c 5`, `-c5`, `--count 5`).
+#[derive(Clone, PartialEq, Debug, Default)]
+pub(super) struct Args {
+    flags: Vec<(String, Option<String>)>,
+    pub(super) positional: Vec<String>,
+}
+
+impl Args {
+    // argv[0] is the command name and is skipped
+    pub(super) fn parse(argv: &[String], takes_value: &[&str]) -> Self {
+        let mut args = Args::default();
+        let mut rest = argv.iter().skip(1);
+
+        while let Some(arg) = rest.next() {
+            if arg == "--" {
+                args.positional.extend(rest.cloned());
+                break;
+            }
+            if let Some(long) = arg.strip_prefix("--") {
+                let (name, value) = match long.split_once('=') {
+                    Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                    None if takes_value.contains(&long) => (long.to_string(), rest.next().cloned()),
+                    None => (long.to_string(), None),
+                };
+                args.flags.push((name, value));
+                continue;
+            }
+            // lone `-` and `-5` style numbers are positional
+            let short = match arg.strip_prefix('-') {
+                Some(short) if !short.is_empty() && !short.chars().all(|c| c.is_ascii_digit()) => short,
+                _ => {
+                    args.positional.push(arg.clone());
+                    continue;
+                }
+            };
+            for (i, c) in short.char_indices() {
+                let name = c.to_string();
+                if takes_value.contains(&name.as_str()) {
+                    let attached = &short[i + c.len_utf8()..];
+                    let value = if attached.is_empty() { rest.next().cloned() } else { Some(attached.to_string()) };
+                    args.flags.push((name, value));
+                    break;
+                }
+                args.flags.push((name, None));
+            }
+        }
+        args
+    }
+
+    // true if any of the given names was passed
+    pub(super) fn has(&self, names: &[&str]) -> bool {
+        self.flags.iter().any(|(name, _)| names.contains(&name.as_str()))
+    }
+
+    // last value given for any of the names - Ok(None) when none of them
+    // was passed, an error when one ended the line without its value
+    pub(super) fn value(&self, names: &[&str]) -> Result<Option<&str>, MissingArgument> {
+        match self.flags.iter().rev().find(|(name, _)| names.contains(&name.as_str())) {
+            None => Ok(None),
+            Some((_, Some(value))) => Ok(Some(value)),
+            Some((name, None)) => Err(MissingArgument(name.clone())),
+        }
+    }
+}
+
+// a value flag with nothing after it, e.g. `head -n`
+#[derive(Clone, PartialEq, Debug)]
+pub(super) struct MissingArgument(String);
+
+impl fmt::Display for MissingArgument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.chars().count() {
+            1 => write!(f, "option requires an argument -- '{}'", self.0),
+            _ => write!(f, "option '--{}' requires an argument", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_line(line: &str) -> Result<Vec<Pipeline>, SyntaxError> {
+        tokenize(line).and_then(parse)
+    }
+
+    // (connector, argv of each stage) per pipeline, words expanded with no variables set
+    fn shape(line: &str) -> Vec<(Connector, Vec<Vec<String>>)> {
+        parse_line(line)
+            .unwrap()
+            .into_iter()
+            .map(|p| (p.connector, p.stages.iter().map(|argv| argv.iter().map(|w| w.expand(|_| None)).collect()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_lists_and_pipelines() {
+        let words = |argv: &[&str]| argv.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        assert_eq!(shape(""), vec![]);
+        assert_eq!(shape("a;"), vec![(Connector::Always, vec![words(&["a"])])]);
+        assert_eq!(
+            shape("a x && b | c || d; e"),
+            vec![
+                (Connector::Always, vec![words(&["a", "x"])]),
+                (Connector::And, vec![words(&["b"]), words(&["c"])]),
+                (Connector::Or, vec![words(&["d"])]),
+                (Connector::Always, vec![words(&["e"])]),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_dangling_operators() {
+        let cases = [
+            ("a &&", "&&"),
+            ("a ||", "||"),
+            ("a |", "|"),
+            ("&& a", "&&"),
+            ("|| a", "||"),
+            ("| a", "|"),
+            ("; a", ";"),
+            ("a ;; b", ";"),
+            ("a; ;", ";"),
+            ("a && ; b", ";"),
+            ("false && ; echo hi", ";"),
+            ("a || ; b", ";"),
+            ("a | ; b", ";"),
+            ("a | && b", "&&"),
+            ("a >", "newline"),
+            ("a > | b", "|"),
+        ];
+        for (line, token) in cases {
+            assert_eq!(parse_line(line), Err(SyntaxError::UnexpectedToken(token)), "{}", line);
+        }
+    }
+}
@@ -0,0 +1,559 @@
+// an in-memory filesystem built from the portfolio content, so the
+// visitor can `cd` around projects the way they would on a real box.
+// content files are rendered when read, so they always match the active
+// content - they are read-only, the visitor's own files come from tmpfs.
+
+use leptos::*;
+
+use super::commands::{Category, Command, Context, Output, Registry};
+use super::content::portfolio;
+use super::portfolio::{slugify, Portfolio};
+use super::table::{Cell, Table};
+use super::tmpfs;
+use super::tokenizer::Args;
+use super::{get_about_output, get_project_card, get_skill_area_output, TerminalLine};
+
+pub(super) const HOME: &str = "/home/vanguard";
+
+#[derive(Clone, PartialEq, Debug)]
+pub(super) enum File {
+    Readme,
+    About,
+    Contact,
+    Env,
+    ProjectReadme(usize),
+    ProjectLinks(usize),
+    Skills(usize),
+    // written by the visitor, see tmpfs
+    Text(String),
+}
+
+pub(super) enum Node {
+    Dir(Vec<(String, Node)>),
+    File(File),
+}
+
+impl Node {
+    pub(super) fn is_dir(&self) -> bool {
+        matches!(self, Node::Dir(_))
+    }
+
+    // put `node` at the absolute path `abs`, creating missing directories on
+    // the way. an existing file is replaced, an existing directory kept
+    pub(super) fn insert(&mut self, abs: &str, node: Node) {
+        let mut parts: Vec<&str> = abs.split('/').filter(|p| !p.is_empty()).collect();
+        let Some(name) = parts.pop() else {
+            return;
+        };
+        let mut dir = self;
+        for part in parts {
+            let Node::Dir(children) = dir else {
+                return;
+            };
+            let i = match children.iter().position(|(n, _)| n == part) {
+                Some(i) => i,
+                None => {
+                    children.push((part.to_string(), Node::Dir(vec![])));
+                    children.len() - 1
+                }
+            };
+            dir = &mut children[i].1;
+        }
+        let Node::Dir(children) = dir else {
+            return;
+        };
+        match children.iter().position(|(n, _)| n == name) {
+            Some(_) if node.is_dir() => {}
+            Some(i) => children[i].1 = node,
+            None => children.push((name.to_string(), node)),
+        }
+    }
+
+    // children sorted like `ls`, dotfiles only when asked for
+    pub(super) fn entries(&self, all: bool) -> Vec<(&str, &Node)> {
+        let Node::Dir(children) = self else {
+            return vec![];
+        };
+        let mut entries: Vec<(&str, &Node)> = children
+            .iter()
+            .filter(|(name, _)| all || !name.starts_with('.'))
+            .map(|(name, node)| (name.as_str(), node))
+            .collect();
+        entries.sort_by_key(|(name, _)| name.trim_start_matches('.').to_lowercase());
+        entries
+    }
+
+    fn mode(&self) -> &'static str {
+        match self {
+            Node::Dir(_) => "drwxr-xr-x",
+            Node::File(File::Env) => "-r--------",
+            Node::File(File::Text(_)) => "-rw-rw-r--",
+            Node::File(_) => "-r--r--r--",
+        }
+    }
+}
+
+// /
+// └── home/vanguard (~)
+//     ├── README.md, about.md, contact.vcf, .env
+//     ├── projects/<slug>/{README.md, LINKS}
+//     └── skills/<area>.md
+pub(super) fn root(portfolio: &Portfolio) -> Node {
+    let projects = portfolio
+        .projects
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let files = vec![
+                ("README.md".to_string(), Node::File(File::ProjectReadme(i))),
+                ("LINKS".to_string(), Node::File(File::ProjectLinks(i))),
+            ];
+            (p.slug(), Node::Dir(files))
+        })
+        .collect();
+    let skills = portfolio
+        .skills
+        .iter()
+        .enumerate()
+        .map(|(i, area)| (format!("{}.md", slugify(&area.area)), Node::File(File::Skills(i))))
+        .collect();
+    let home = Node::Dir(vec![
+        ("README.md".into(), Node::File(File::Readme)),
+        ("about.md".into(), Node::File(File::About)),
+        ("contact.vcf".into(), Node::File(File::Contact)),
+        (".env".into(), Node::File(File::Env)),
+        ("projects".into(), Node::Dir(projects)),
+        ("skills".into(), Node::Dir(skills)),
+    ]);
+    Node::Dir(vec![("home".into(), Node::Dir(vec![("vanguard".into(), home)]))])
+}
+
+// the content tree with the visitor's files mounted over it - what every
+// command sees
+pub(super) fn tree(portfolio: &Portfolio) -> Node {
+    let mut root = root(portfolio);
+    tmpfs::mount(&mut root);
+    root
+}
+
+// normalize `path` against `cwd` - `~`, `.` and `..` are handled, the
+// result is absolute and does not need to exist
+pub(super) fn resolve(cwd: &str, path: &str) -> String {
+    let path = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("{}{}", HOME, rest),
+        _ => path.to_string(),
+    };
+    let joined = if path.starts_with('/') { path } else { format!("{}/{}", cwd, path) };
+    let mut parts: Vec<&str> = vec![];
+    for part in joined.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    format!("/{}", parts.join("/"))
+}
+
+// walk an absolute path, names match case-insensitively.
+// returns the path with the names as they are spelled in the tree
+pub(super) fn lookup<'a>(root: &'a Node, abs: &str) -> Option<(String, &'a Node)> {
+    let mut node = root;
+    let mut canonical = String::new();
+    for part in abs.split('/').filter(|p| !p.is_empty()) {
+        let Node::Dir(children) = node else {
+            return None;
+        };
+        let (name, child) = children.iter().find(|(name, _)| name.eq_ignore_ascii_case(part))?;
+        canonical.push('/');
+        canonical.push_str(name);
+        node = child;
+    }
+    if canonical.is_empty() {
+        canonical.push('/');
+    }
+    Some((canonical, node))
+}
+
+pub(super) fn join(dir: &str, name: &str) -> String {
+    format!("{}/{}", dir.trim_end_matches('/'), name)
+}
+
+// `node` and everything below it, each with its path built from `path` -
+// in `ls` order, dotfiles only when `all`. an empty `path` gives paths
+// relative to `node`, the way `grep -r` without a path prints them
+pub(super) fn descend<'a>(node: &'a Node, path: &str, all: bool, out: &mut Vec<(String, &'a Node)>) {
+    out.push((path.to_string(), node));
+    for (name, child) in node.entries(all) {
+        let child_path = if path.is_empty() { name.to_string() } else { join(path, name) };
+        descend(child, &child_path, all, out);
+    }
+}
+
+// shell-style `*` and `?` wildcards, for `find -name`
+fn glob_match(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => glob_match(&pattern[1..], name) || (!name.is_empty() && glob_match(pattern, &name[1..])),
+        (Some('?'), Some(_)) => glob_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(c)) if p == c => glob_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+// how a path is shown in the prompt and title - home becomes `~`
+pub(super) fn display(abs: &str) -> String {
+    match abs.strip_prefix(HOME) {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("~{}", rest),
+        _ => abs.to_string(),
+    }
+}
+
+fn read(file: &File, portfolio: &Portfolio) -> Result<Vec<TerminalLine>, &'static str> {
+    let blank = || TerminalLine::text("", "", false);
+    let lines = match file {
+        File::Readme => vec![
+            blank(),
+            TerminalLine::text("", "  # vanguard secure solutions", false),
+            TerminalLine::text("", "  > engineering complete systems", false),
+            blank(),
+            TerminalLine::text("", "  try: ls, cd projects, tree, cat about.md", false),
+            TerminalLine::text("", "  ~ and /tmp are writable: touch, mkdir, echo hi > notes.txt", false),
+            blank(),
+        ],
+        File::About => get_about_output(),
+        File::Contact => {
+            let contact = &portfolio.contact;
+            [
+                "BEGIN:VCARD".to_string(),
+                "VERSION:4.0".to_string(),
+                format!("FN:{}", portfolio.profile.name),
+                format!("TITLE:{}", portfolio.profile.title),
+                format!("EMAIL:{}", contact.email),
+                format!("URL:{}", contact.github_url()),
+                "END:VCARD".to_string(),
+            ]
+            .iter()
+            .map(|line| TerminalLine::text("", &format!("  {}", line), false))
+            .collect()
+        }
+        File::Env => return Err("Permission denied"),
+        File::ProjectReadme(i) => get_project_card(&portfolio.projects[*i], &portfolio.projects),
+        File::ProjectLinks(i) => {
+            let project = &portfolio.projects[*i];
+            let mut table = Table::new();
+            if let Some(url) = &project.demo_url {
+                table.push(vec!["demo".into(), Cell::link(url, url)]);
+            }
+            table.push(vec!["github".into(), Cell::link(&project.github_url, &project.github_url)]);
+            table.render()
+        }
+        File::Skills(i) => get_skill_area_output(&portfolio.skills[*i]),
+        File::Text(text) => text.lines().map(|line| TerminalLine::text("", &format!("  {}", line), false)).collect(),
+    };
+    Ok(lines)
+}
+
+// a file as plain text, one line per output line without the indent -
+// what `cp` copies and `>` writes
+pub(super) fn text(file: &File, portfolio: &Portfolio) -> Result<String, &'static str> {
+    match file {
+        File::Text(text) => Ok(text.clone()),
+        file => read(file, portfolio).map(|lines| to_text(&lines)),
+    }
+}
+
+pub(super) fn to_text(lines: &[TerminalLine]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            let text = line.plain_text();
+            format!("{}\n", text.strip_prefix("  ").unwrap_or(&text))
+        })
+        .collect()
+}
+
+// Tab candidates for a partly typed path - `pro` -> `projects/`,
+// `projects/gu` -> `projects/guardian-one-platform/`
+pub(super) fn complete_path(cwd: &str, partial: &str) -> Vec<String> {
+    let dir = partial.rsplit_once('/').map_or("", |(dir, _)| dir);
+    let base = if partial.contains('/') { format!("{}/", dir) } else { String::new() };
+    let root = tree(&portfolio());
+    let target = if partial.starts_with('/') && dir.is_empty() { "/".to_string() } else { resolve(cwd, dir) };
+    let Some((_, node)) = lookup(&root, &target) else {
+        return vec![];
+    };
+    let hidden = partial.rsplit('/').next().is_some_and(|name| name.starts_with('.'));
+    node.entries(hidden)
+        .into_iter()
+        .map(|(name, child)| format!("{}{}{}", base, name, if child.is_dir() { "/" } else { "" }))
+        .collect()
+}
+
+// commands
+
+pub(super) struct Cd;
+
+impl Command for Cd {
+    fn name(&self) -> &'static str { "cd" }
+    fn summary(&self) -> &'static str { "Change directory" }
+    fn usage(&self) -> &'static str { "cd [dir]" }
+    fn description(&self) -> &'static str {
+        "Changes the working directory shown in the prompt and the window title. With no DIR, goes home (~). Names match case-insensitively."
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["cd projects", "cd ..", "cd"] }
+    fn takes_paths(&self) -> bool { true }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let target = resolve(&ctx.cwd.get_untracked(), argv.get(1).map_or("~", String::as_str));
+        let root = tree(&portfolio());
+        match lookup(&root, &target) {
+            Some((path, node)) if node.is_dir() => {
+                ctx.cwd.set(path);
+                Output::ok(vec![])
+            }
+            Some(_) => Output::error(1, &format!("cd: not a directory: {}", argv[1])),
+            None => Output::error(1, &format!("cd: no such file or directory: {}", argv[1])),
+        }
+    }
+}
+
+pub(super) struct Pwd;
+
+impl Command for Pwd {
+    fn name(&self) -> &'static str { "pwd" }
+    fn summary(&self) -> &'static str { "Print working directory" }
+    fn usage(&self) -> &'static str { "pwd" }
+    fn category(&self) -> Category { Category::Utility }
+
+    fn run(&self, _argv: &[String], ctx: &Context) -> Output {
+        vec![TerminalLine::text("", &format!("  {}", ctx.cwd.get_untracked()), false)].into()
+    }
+}
+
+pub(super) struct Ls;
+
+impl Command for Ls {
+    fn name(&self) -> &'static str { "ls" }
+    fn summary(&self) -> &'static str { "List directory contents" }
+    fn usage(&self) -> &'static str { "ls [-la] [path...]" }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-l", "Long listing with permissions"),
+            ("-a", "Include hidden files"),
+        ]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["ls", "ls -la", "ls projects"] }
+    fn takes_paths(&self) -> bool { true }
+
+    fn complete(&self, _words: &[String], _registry: &Registry) -> Vec<String> {
+        vec!["-la".into()]
+    }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &[]);
+        let (long, all) = (args.has(&["l"]), args.has(&["a"]));
+        let cwd = ctx.cwd.get_untracked();
+        let root = tree(&portfolio());
+        let paths = if args.positional.is_empty() { vec![".".to_string()] } else { args.positional.clone() };
+
+        let mut status = 0;
+        let mut lines = vec![TerminalLine::text("", "", false)];
+        for (i, path) in paths.iter().enumerate() {
+            let Some((_, node)) = lookup(&root, &resolve(&cwd, path)) else {
+                lines.push(TerminalLine::text("", &format!("  ls: cannot access '{}': No such file or directory", path), false));
+                status = 2;
+                continue;
+            };
+            let entries = match node {
+                Node::Dir(_) => node.entries(all),
+                Node::File(_) => vec![(path.as_str(), node)],
+            };
+            if paths.len() > 1 && node.is_dir() {
+                if i > 0 {
+                    lines.push(TerminalLine::text("", "", false));
+                }
+                lines.push(TerminalLine::bold("", &format!("  {}:", path)));
+            }
+            // directories in bold with a trailing slash
+            let name = |(name, node): &(&str, &Node)| match node.is_dir() {
+                true => Cell::bold(&format!("{}/", name)),
+                false => Cell::from(*name),
+            };
+            let mut table = Table::new();
+            if long {
+                for entry in &entries {
+                    table.push(vec![entry.1.mode().into(), name(entry)]);
+                }
+            } else {
+                for row in entries.chunks(3) {
+                    table.push(row.iter().map(name).collect());
+                }
+            }
+            lines.extend(table.render());
+        }
+        lines.push(TerminalLine::text("", "", false));
+        Output::new(status, lines)
+    }
+}
+
+pub(super) struct Cat;
+
+impl Command for Cat {
+    fn name(&self) -> &'static str { "cat" }
+    fn summary(&self) -> &'static str { "Print a file" }
+    fn usage(&self) -> &'static str { "cat <file...>" }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["cat README.md", "cat projects/raft-consensus-cluster/README.md"] }
+    fn takes_paths(&self) -> bool { true }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        if argv.len() < 2 {
+            return Output::error(2, &format!("usage: {}", self.usage()));
+        }
+        let cwd = ctx.cwd.get_untracked();
+        let portfolio = portfolio();
+        let root = tree(&portfolio);
+
+        let mut status = 0;
+        let mut lines = vec![];
+        for path in &argv[1..] {
+            let error = match lookup(&root, &resolve(&cwd, path)) {
+                Some((_, Node::File(file))) => match read(file, &portfolio) {
+                    Ok(content) => {
+                        lines.extend(content);
+                        continue;
+                    }
+                    Err(err) => err,
+                },
+                Some((_, Node::Dir(_))) => "Is a directory",
+                None => "No such file or directory",
+            };
+            lines.push(TerminalLine::text("", &format!("  cat: {}: {}", path, error), false));
+            status = 1;
+        }
+        Output::new(status, lines)
+    }
+}
+
+pub(super) struct Tree;
+
+impl Command for Tree {
+    fn name(&self) -> &'static str { "tree" }
+    fn summary(&self) -> &'static str { "Show a directory tree" }
+    fn usage(&self) -> &'static str { "tree [-a] [dir]" }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-a", "Include hidden files")]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["tree", "tree projects"] }
+    fn takes_paths(&self) -> bool { true }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        let args = Args::parse(argv, &[]);
+        let path = args.positional.first().map_or(".", String::as_str);
+        let root = tree(&portfolio());
+        let Some((abs, node)) = lookup(&root, &resolve(&ctx.cwd.get_untracked(), path)) else {
+            return Output::error(2, &format!("tree: {}: No such file or directory", path));
+        };
+
+        let mut lines = vec![TerminalLine::text("", "", false), TerminalLine::bold("", &format!("  {}", display(&abs)))];
+        let (mut dirs, mut files) = (0, 0);
+        walk(node, "  ", args.has(&["a"]), &mut lines, &mut dirs, &mut files);
+        lines.push(TerminalLine::text("", "", false));
+        lines.push(TerminalLine::text("", &format!("  {} directories, {} files", dirs, files), false));
+        lines.push(TerminalLine::text("", "", false));
+        lines.into()
+    }
+}
+
+fn walk(node: &Node, indent: &str, all: bool, lines: &mut Vec<TerminalLine>, dirs: &mut usize, files: &mut usize) {
+    let entries = node.entries(all);
+    for (i, (name, child)) in entries.iter().enumerate() {
+        let last = i + 1 == entries.len();
+        let branch = if last { "└── " } else { "├── " };
+        if child.is_dir() {
+            *dirs += 1;
+            lines.push(TerminalLine::text("", &format!("{}{}{}/", indent, branch, name), false));
+            walk(child, &format!("{}{}", indent, if last { "    " } else { "│   " }), all, lines, dirs, files);
+        } else {
+            *files += 1;
+            lines.push(TerminalLine::text("", &format!("{}{}{}", indent, branch, name), false));
+        }
+    }
+}
+
+pub(super) struct Find;
+
+impl Command for Find {
+    fn name(&self) -> &'static str { "find" }
+    fn summary(&self) -> &'static str { "Search for files by name" }
+    fn usage(&self) -> &'static str { "find [path...] [-name GLOB] [-iname GLOB] [-type f|d]" }
+    fn description(&self) -> &'static str {
+        "Lists every file and directory under each PATH (default .) that passes all the tests. GLOB takes * and ? wildcards and is matched against the last path component - quote it so it reaches find as typed."
+    }
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-name GLOB", "Name matches GLOB"),
+            ("-iname GLOB", "Like -name, ignoring case"),
+            ("-type f|d", "Only files, or only directories"),
+        ]
+    }
+    fn category(&self) -> Category { Category::Utility }
+    fn examples(&self) -> &'static [&'static str] { &["find . -name '*.md'", "find projects -type d", "find ~ -iname readme*"] }
+    fn takes_paths(&self) -> bool { true }
+
+    fn complete(&self, _words: &[String], _registry: &Registry) -> Vec<String> {
+        vec!["-name".into(), "-iname".into(), "-type".into()]
+    }
+
+    fn run(&self, argv: &[String], ctx: &Context) -> Output {
+        // find's own syntax - paths first, then `-test value` pairs
+        let split = argv[1..].iter().position(|a| a.starts_with('-')).map_or(argv.len(), |i| i + 1);
+        let paths = if split > 1 { argv[1..split].to_vec() } else { vec![".".to_string()] };
+        let (mut name, mut kind) = (None, None);
+        let mut tests = argv[split..].iter();
+        while let Some(test) = tests.next() {
+            let Some(value) = tests.next() else {
+                return Output::error(1, &format!("find: missing argument to `{}'", test));
+            };
+            match (test.as_str(), value.as_str()) {
+                ("-name", glob) => name = Some((glob.chars().collect::<Vec<_>>(), false)),
+                ("-iname", glob) => name = Some((glob.to_lowercase().chars().collect(), true)),
+                ("-type", "f") => kind = Some(false),
+                ("-type", "d") => kind = Some(true),
+                ("-type", other) => return Output::error(1, &format!("find: unknown argument to -type: {}", other)),
+                (other, _) => return Output::error(1, &format!("find: unknown predicate `{}'", other)),
+            }
+        }
+
+        let cwd = ctx.cwd.get_untracked();
+        let root = tree(&portfolio());
+        let mut status = 0;
+        let mut lines = vec![];
+        for path in &paths {
+            let Some((_, node)) = lookup(&root, &resolve(&cwd, path)) else {
+                lines.push(TerminalLine::text("", &format!("  find: '{}': No such file or directory", path), false));
+                status = 1;
+                continue;
+            };
+            let mut found = vec![];
+            descend(node, path, true, &mut found);
+            for (found_path, node) in found {
+                let base = found_path.rsplit('/').next().unwrap_or_default();
+                let name_ok = name.as_ref().is_none_or(|(glob, fold)| {
+                    let base: Vec<char> = if *fold { base.to_lowercase().chars().collect() } else { base.chars().collect() };
+                    glob_match(glob, &base)
+                });
+                if name_ok && kind.is_none_or(|dir| node.is_dir() == dir) {
+                    lines.push(TerminalLine::text("", &format!("  {}", found_path), false));
+                }
+            }
+        }
+        Output::new(status, lines)
+    }
+}